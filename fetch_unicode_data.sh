@@ -0,0 +1,64 @@
+#!/bin/sh
+#
+# Download the Unicode Character Database files that generate_unicode_tables!
+# reads at macro-expansion time, into $UNICODE_DATA_DIR or, if it's unset, into
+# data/ in this crate.
+#
+# Usage: ./fetch_unicode_data.sh [VERSION]
+#
+# VERSION defaults to the Unicode version the tests are written against.
+
+set -eu
+
+VERSION="${1:-15.1.0}"
+BASE_URL="https://www.unicode.org/Public"
+
+cd "$(dirname "$0")"
+DATA_DIR="${UNICODE_DATA_DIR:-data}"
+mkdir -p "$DATA_DIR"
+
+fetch() {
+    echo "Fetching $1/$2"
+    curl --fail --silent --show-error --location --output "$DATA_DIR/$2" "$BASE_URL/$1/$2"
+}
+
+for file in \
+    ArabicShaping.txt \
+    BidiBrackets.txt \
+    BidiMirroring.txt \
+    Blocks.txt \
+    DerivedAge.txt \
+    DerivedCoreProperties.txt \
+    DerivedNormalizationProps.txt \
+    EastAsianWidth.txt \
+    HangulSyllableType.txt \
+    LineBreak.txt \
+    NameAliases.txt \
+    PropList.txt \
+    PropertyValueAliases.txt \
+    ScriptExtensions.txt \
+    Scripts.txt \
+    UnicodeData.txt
+do
+    fetch "$VERSION/ucd" "$file"
+done
+
+for file in \
+    GraphemeBreakProperty.txt \
+    SentenceBreakProperty.txt \
+    WordBreakProperty.txt
+do
+    fetch "$VERSION/ucd/auxiliary" "$file"
+done
+
+fetch "$VERSION/ucd/extracted" DerivedBidiClass.txt
+fetch "$VERSION/ucd/emoji" emoji-data.txt
+fetch "idna/$VERSION" IdnaMappingTable.txt
+
+for file in \
+    IdentifierStatus.txt \
+    IdentifierType.txt \
+    confusables.txt
+do
+    fetch "security/$VERSION" "$file"
+done
@@ -0,0 +1,109 @@
+//! Generate a function returning the Age property of code points, i.e. the
+//! version of Unicode in which each was assigned, and the version of Unicode
+//! from which all tables are generated.
+
+use crate::range_table;
+use crate::ucd;
+use quote::quote;
+use std::ops::RangeInclusive;
+
+/// Parse a Unicode version such as "15.1" or "15.1.0" into its components.
+/// Missing components are 0.
+fn parse_version(s: &str) -> (u8, u8, u8) {
+    let mut components = s.split('.').map(|component| {
+        component
+            .parse()
+            .unwrap_or_else(|_| panic!("bad version: {:?}", s))
+    });
+    let major = components.next().expect("empty version");
+    let minor = components.next().unwrap_or(0);
+    let update = components.next().unwrap_or(0);
+    assert!(components.next().is_none(), "bad version: {:?}", s);
+    (major, minor, update)
+}
+
+#[test]
+fn check_parse_version() {
+    assert_eq!(parse_version("1.1"), (1, 1, 0));
+    assert_eq!(parse_version("15.1.0"), (15, 1, 0));
+}
+
+/// Return the version of Unicode of DerivedAge.txt, given in its first line,
+/// e.g. "# DerivedAge-15.1.0.txt".
+fn unicode_version(contents: &str) -> (u8, u8, u8) {
+    let version = contents
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("# DerivedAge-"))
+        .and_then(|line| line.strip_suffix(".txt"))
+        .expect("DerivedAge.txt doesn't start with its versioned name");
+    parse_version(version)
+}
+
+/// Compute the ranges of code points assigned in each version of Unicode, in
+/// code point order, merging adjacent ranges with the same age.
+fn compute_age_ranges(contents: &str) -> Vec<(RangeInclusive<u32>, proc_macro2::TokenStream)> {
+    let mut ranges: Vec<(RangeInclusive<u32>, (u8, u8))> = ucd::data_lines(contents)
+        .map(|fields| {
+            let (major, minor, _) = parse_version(fields[1]);
+            (ucd::parse_code_point_range(fields[0]), (major, minor))
+        })
+        .collect();
+    ranges.sort_by_key(|(range, _)| *range.start());
+
+    let mut merged: Vec<(RangeInclusive<u32>, (u8, u8))> = vec![];
+    for (range, age) in ranges {
+        match merged.last_mut() {
+            Some((last, last_age)) if *last.end() + 1 == *range.start() && *last_age == age => {
+                *last = *last.start()..=*range.end();
+            }
+            _ => merged.push((range, age)),
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|(range, (major, minor))| (range, quote!((#major, #minor))))
+        .collect()
+}
+
+/// Generate `UNICODE_VERSION`, and the `age` function and the table backing
+/// it.
+pub fn generate_age_function() -> proc_macro2::TokenStream {
+    let contents = ucd::read_data_file("DerivedAge.txt");
+
+    let (major, minor, update) = unicode_version(&contents);
+
+    let age_fn = range_table::generate_range_lookup(
+        quote!((u8, u8)),
+        "age_ranges",
+        "age",
+        r#"
+Return the Age property of the provided code point: the major and minor version
+of Unicode in which it was assigned, or `None` if it's unassigned.
+
+```text
+assert_eq!(age('a' as u32), Some((1, 1)));
+assert_eq!(age(0x20AC), Some((2, 1))); // EURO SIGN
+assert_eq!(age(0x0378), None);
+```
+"#
+        .trim(),
+        &compute_age_ranges(&contents),
+    );
+
+    quote! {
+        /// The version of Unicode of the data files from which these tables
+        /// were generated, as its major, minor and update version numbers,
+        /// taken from DerivedAge.txt.
+        ///
+        /// This covers only tables generated from the files in the Unicode
+        /// data directory.  The tables computed by `unicode_info` (character
+        /// info, case mapping and folding, special casing and the ECMAScript
+        /// identifier tables) come from the data it bundles, which may be a
+        /// different version.
+        pub const UNICODE_VERSION: (u8, u8, u8) = (#major, #minor, #update);
+
+        #age_fn
+    }
+}
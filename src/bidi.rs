@@ -0,0 +1,232 @@
+//! Generate functions returning the Bidi_Class, Bidi_Mirrored,
+//! Bidi_Mirroring_Glyph, Bidi_Paired_Bracket and Bidi_Paired_Bracket_Type
+//! properties used by the Unicode Bidirectional Algorithm (UAX #9).
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::ucd;
+use crate::ucd::UnicodeDataRecord;
+use quote::quote;
+
+const BIDI_CLASS: &str = "BidiClass";
+
+const BIDI_PAIRED_BRACKET_TYPE: &str = "BidiPairedBracketType";
+
+/// The short and long names of each Bidi_Class value, and its `BidiClass`
+/// variant.  DerivedBidiClass.txt uses the short names in its data lines and
+/// the long names in its `@missing` lines.
+const BIDI_CLASS_VALUES: [(&str, &str, &str); 23] = [
+    ("L", "Left_To_Right", "L"),
+    ("R", "Right_To_Left", "R"),
+    ("AL", "Arabic_Letter", "Al"),
+    ("EN", "European_Number", "En"),
+    ("ES", "European_Separator", "Es"),
+    ("ET", "European_Terminator", "Et"),
+    ("AN", "Arabic_Number", "An"),
+    ("CS", "Common_Separator", "Cs"),
+    ("NSM", "Nonspacing_Mark", "Nsm"),
+    ("BN", "Boundary_Neutral", "Bn"),
+    ("B", "Paragraph_Separator", "B"),
+    ("S", "Segment_Separator", "S"),
+    ("WS", "White_Space", "Ws"),
+    ("ON", "Other_Neutral", "On"),
+    ("LRE", "Left_To_Right_Embedding", "Lre"),
+    ("LRO", "Left_To_Right_Override", "Lro"),
+    ("RLE", "Right_To_Left_Embedding", "Rle"),
+    ("RLO", "Right_To_Left_Override", "Rlo"),
+    ("PDF", "Pop_Directional_Format", "Pdf"),
+    ("LRI", "Left_To_Right_Isolate", "Lri"),
+    ("RLI", "Right_To_Left_Isolate", "Rli"),
+    ("FSI", "First_Strong_Isolate", "Fsi"),
+    ("PDI", "Pop_Directional_Isolate", "Pdi"),
+];
+
+/// The `BidiPairedBracketType` variant for each value in BidiBrackets.txt.
+/// `None` is the value of all unlisted code points.
+const BIDI_PAIRED_BRACKET_TYPE_VALUES: [(&str, &str); 3] =
+    [("n", "None"), ("o", "Open"), ("c", "Close")];
+
+/// Return the `BidiClass` variant for a Bidi_Class value given by either its
+/// short or its long name.
+fn bidi_class_variant(value: &str) -> &'static str {
+    BIDI_CLASS_VALUES
+        .iter()
+        .find(|(short, long, _)| *short == value || *long == value)
+        .unwrap_or_else(|| panic!("unexpected Bidi_Class value {:?}", value))
+        .2
+}
+
+/// Compute the Bidi_Class of every code point from DerivedBidiClass.txt.
+/// Unlike UnicodeData.txt, it gives unassigned code points their values: the
+/// default for their block (e.g. R in Hebrew blocks), or BN for
+/// Default_Ignorable_Code_Point and noncharacter code points.
+fn compute_bidi_classes() -> Vec<PropertyValue> {
+    let mut values = vec![PropertyValue::new(BIDI_CLASS, "L"); ucd::CODE_POINT_LIMIT as usize];
+
+    for (range, value) in ucd::read_property_file_with_defaults("DerivedBidiClass.txt") {
+        let variant = bidi_class_variant(&value);
+        for code in range {
+            values[code as usize] = PropertyValue::new(BIDI_CLASS, variant);
+        }
+    }
+
+    values
+}
+
+/// Compute the Bidi_Mirrored property of every code point.
+fn compute_bidi_mirrored(unicode_data: &[UnicodeDataRecord]) -> Vec<bool> {
+    let mut values = vec![false; ucd::CODE_POINT_LIMIT as usize];
+    for record in unicode_data {
+        for code in record.range.clone() {
+            values[code as usize] = record.bidi_mirrored;
+        }
+    }
+
+    values
+}
+
+/// Read a file, such as BidiMirroring.txt, whose first two fields are a code
+/// point and the code point it maps to, and return for every code point the
+/// value that must be added to it (with wrapping) to give the code point it
+/// maps to.  The value for unlisted code points is 0.
+fn compute_mapping_deltas(name: &str) -> Vec<u32> {
+    let mut deltas = vec![0u32; ucd::CODE_POINT_LIMIT as usize];
+
+    let contents = ucd::read_data_file(name);
+    for fields in ucd::data_lines(&contents) {
+        let code = ucd::parse_code_point(fields[0]);
+        let mapped = ucd::parse_code_point(fields[1]);
+        assert_ne!(code, mapped, "{:04X} maps to itself in {}", code, name);
+        deltas[code as usize] = mapped.wrapping_sub(code);
+    }
+
+    deltas
+}
+
+/// Compute the Bidi_Paired_Bracket_Type of every code point.
+fn compute_bidi_paired_bracket_types() -> Vec<PropertyValue> {
+    let mut values =
+        vec![PropertyValue::new(BIDI_PAIRED_BRACKET_TYPE, "None"); ucd::CODE_POINT_LIMIT as usize];
+
+    let contents = ucd::read_data_file("BidiBrackets.txt");
+    for fields in ucd::data_lines(&contents) {
+        let variant = property_enum::variant_for(&BIDI_PAIRED_BRACKET_TYPE_VALUES, fields[2]);
+        let code = ucd::parse_code_point(fields[0]);
+        values[code as usize] = PropertyValue::new(BIDI_PAIRED_BRACKET_TYPE, variant);
+    }
+
+    values
+}
+
+/// Generate the `BidiClass` and `BidiPairedBracketType` enums, the functions
+/// returning the bidi properties of code points, and the tables backing them.
+pub fn generate_bidi_functions(unicode_data: &[UnicodeDataRecord]) -> proc_macro2::TokenStream {
+    let bidi_class_enum = property_enum::generate_property_enum(
+        BIDI_CLASS,
+        "A value of the Bidi_Class property, as used in UAX #9.",
+        &BIDI_CLASS_VALUES
+            .iter()
+            .map(|(_, _, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let bidi_paired_bracket_type_enum = property_enum::generate_property_enum(
+        BIDI_PAIRED_BRACKET_TYPE,
+        "A value of the Bidi_Paired_Bracket_Type property, as used in UAX #9.",
+        &BIDI_PAIRED_BRACKET_TYPE_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let bidi_class_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(BidiClass),
+        "bidiclass",
+        "bidi_class",
+        "Return the Bidi_Class property of the provided code point.",
+        &compute_bidi_classes(),
+    );
+
+    let is_bidi_mirrored_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(bool),
+        "bidimirrored",
+        "is_bidi_mirrored",
+        "Return true iff the provided code point has the Bidi_Mirrored property.",
+        &compute_bidi_mirrored(unicode_data),
+    );
+
+    let bidi_mirroring_delta_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(u32),
+        "bidimirroring",
+        "bidi_mirroring_delta",
+        r#"
+Return the value that must be added (with wrapping) to the provided code point
+to give its Bidi_Mirroring_Glyph, or 0 if it has none.  `bidi_mirroring_glyph`
+is usually more convenient.
+"#
+        .trim(),
+        &compute_mapping_deltas("BidiMirroring.txt"),
+    );
+
+    let bidi_paired_bracket_delta_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(u32),
+        "bidibracket",
+        "bidi_paired_bracket_delta",
+        r#"
+Return the value that must be added (with wrapping) to the provided code point
+to give its Bidi_Paired_Bracket, or 0 if it isn't a paired bracket.
+`bidi_paired_bracket` is usually more convenient.
+"#
+        .trim(),
+        &compute_mapping_deltas("BidiBrackets.txt"),
+    );
+
+    let bidi_paired_bracket_type_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(BidiPairedBracketType),
+        "bidibrackettype",
+        "bidi_paired_bracket_type",
+        "Return the Bidi_Paired_Bracket_Type property of the provided code point.",
+        &compute_bidi_paired_bracket_types(),
+    );
+
+    quote! {
+        #bidi_class_enum
+
+        #bidi_paired_bracket_type_enum
+
+        #bidi_class_fn
+
+        #is_bidi_mirrored_fn
+
+        #bidi_mirroring_delta_fn
+
+        #bidi_paired_bracket_delta_fn
+
+        #bidi_paired_bracket_type_fn
+
+        /// Return the Bidi_Mirroring_Glyph of the provided code point: the
+        /// code point whose glyph is typically a mirror image of its glyph, if
+        /// there is one.
+        ///
+        /// ```text
+        /// assert_eq!(bidi_mirroring_glyph('(' as u32), Some(')' as u32));
+        /// assert_eq!(bidi_mirroring_glyph('a' as u32), None);
+        /// ```
+        pub fn bidi_mirroring_glyph(code: u32) -> Option<u32> {
+            match bidi_mirroring_delta(code) {
+                0 => None,
+                delta => Some(code.wrapping_add(delta)),
+            }
+        }
+
+        /// Return the Bidi_Paired_Bracket of the provided code point: the
+        /// opening or closing bracket that pairs with it, if it is a bracket.
+        pub fn bidi_paired_bracket(code: u32) -> Option<u32> {
+            match bidi_paired_bracket_delta(code) {
+                0 => None,
+                delta => Some(code.wrapping_add(delta)),
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Generate a function returning the Block property of code points.
+
+use crate::range_table;
+use crate::ucd;
+use quote::quote;
+
+/// Generate the `block` function and the table backing it.
+pub fn generate_block_function() -> proc_macro2::TokenStream {
+    let contents = ucd::read_data_file("Blocks.txt");
+
+    let mut blocks: Vec<_> = ucd::data_lines(&contents)
+        .map(|fields| (ucd::parse_code_point_range(fields[0]), fields[1]))
+        .collect();
+    blocks.sort_by_key(|(range, _)| *range.start());
+
+    range_table::generate_range_lookup(
+        quote!(&'static str),
+        "block_ranges",
+        "block",
+        r#"
+Return the name of the Block containing the provided code point, e.g. "Basic
+Latin", or `None` if it isn't in a block (i.e. its Block is No_Block).
+
+```text
+assert_eq!(block('a' as u32), Some("Basic Latin"));
+assert_eq!(block(0x4E00), Some("CJK Unified Ideographs"));
+```
+"#
+        .trim(),
+        &blocks,
+    )
+}
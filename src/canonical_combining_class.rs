@@ -0,0 +1,44 @@
+//! Generate a function returning the Canonical_Combining_Class of a code
+//! point.
+
+use crate::code_point_lookup;
+use crate::ucd::{UnicodeDataRecord, CODE_POINT_LIMIT};
+use quote::quote;
+
+/// Compute the Canonical_Combining_Class of every code point.  Code points not
+/// listed in UnicodeData.txt have class 0 (Not_Reordered).
+pub fn compute_canonical_combining_classes(unicode_data: &[UnicodeDataRecord]) -> Vec<u8> {
+    let mut classes = vec![0u8; CODE_POINT_LIMIT as usize];
+    for record in unicode_data {
+        for code in record.range.clone() {
+            classes[code as usize] = record.canonical_combining_class;
+        }
+    }
+
+    classes
+}
+
+/// Generate the `canonical_combining_class` function and the tables backing it.
+pub fn generate_canonical_combining_class_function(
+    unicode_data: &[UnicodeDataRecord],
+) -> proc_macro2::TokenStream {
+    let classes = compute_canonical_combining_classes(unicode_data);
+
+    code_point_lookup::generate_code_point_lookup(
+        quote!(u8),
+        "combiningclass",
+        "canonical_combining_class",
+        r#"
+Return the Canonical_Combining_Class of the provided code point: 0 for starters
+(most code points), and a nonzero value for combining marks that participate in
+canonical reordering.
+
+```text
+assert_eq!(canonical_combining_class('a' as u32), 0);
+assert_eq!(canonical_combining_class(0x0301), 230); // COMBINING ACUTE ACCENT
+```
+"#
+        .trim(),
+        &classes,
+    )
+}
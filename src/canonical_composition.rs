@@ -0,0 +1,144 @@
+//! Generate the primary composites of pairs of code points, and a function
+//! converting UTF-16 text to Normalization Form C.
+
+use crate::generate_table;
+use crate::ucd;
+use crate::ucd::UnicodeDataRecord;
+use quote::quote;
+
+/// A pair of code points and the primary composite they compose to, converted
+/// to tokens as a tuple.
+struct Composition {
+    first: u32,
+    second: u32,
+    composite: u32,
+}
+
+impl quote::ToTokens for Composition {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Composition {
+            first,
+            second,
+            composite,
+        } = self;
+        tokens.extend(quote! { (#first, #second, #composite) });
+    }
+}
+
+/// Compute every canonical composition other than of Hangul syllables, sorted
+/// by the pair of code points composed: the code points whose canonical
+/// decomposition mapping in UnicodeData.txt is a pair, less those that are
+/// Full_Composition_Exclusion.
+fn compute_canonical_compositions(unicode_data: &[UnicodeDataRecord]) -> Vec<Composition> {
+    let excluded =
+        ucd::read_binary_property("DerivedNormalizationProps.txt", "Full_Composition_Exclusion");
+
+    let mut compositions: Vec<Composition> = unicode_data
+        .iter()
+        .filter(|record| {
+            record.decomposition_tag.is_none()
+                && record.decomposition.len() == 2
+                && !excluded[*record.range.start() as usize]
+        })
+        .map(|record| Composition {
+            first: record.decomposition[0],
+            second: record.decomposition[1],
+            composite: *record.range.start(),
+        })
+        .collect();
+    compositions.sort_by_key(|composition| (composition.first, composition.second));
+
+    compositions
+}
+
+/// Generate `canonical_composition` and its table, and `to_nfc`.
+pub fn generate_canonical_composition_functions(
+    unicode_data: &[UnicodeDataRecord],
+) -> proc_macro2::TokenStream {
+    let canonical_compositions_table = generate_table::generate_table(
+        quote!((u32, u32, u32)),
+        "canonicalcompositions",
+        "Pairs of code points and the primary composites they compose to, sorted by pair.",
+        &compute_canonical_compositions(unicode_data),
+    );
+
+    quote! {
+        #canonical_compositions_table
+
+        /// Return the primary composite that `first` followed by `second`
+        /// canonically composes to, if any.
+        ///
+        /// ```text
+        /// // U+00C5 LATIN CAPITAL LETTER A WITH RING ABOVE
+        /// assert_eq!(canonical_composition(0x41, 0x30A), Some(0xC5));
+        /// // U+AC00 HANGUL SYLLABLE GA
+        /// assert_eq!(canonical_composition(0x1100, 0x1161), Some(0xAC00));
+        /// // U+0958 DEVANAGARI LETTER QA is a composition exclusion.
+        /// assert_eq!(canonical_composition(0x915, 0x93C), None);
+        /// ```
+        pub fn canonical_composition(first: u32, second: u32) -> Option<u32> {
+            if let Some(syllable) = compose_hangul(first, second) {
+                return Some(syllable);
+            }
+
+            canonicalcompositions
+                .binary_search_by_key(&(first, second), |&(first, second, _)| (first, second))
+                .ok()
+                .map(|index| canonicalcompositions[index].2)
+        }
+
+        /// Apply the canonical composition algorithm to `decomposed`, which is
+        /// in Normalization Form D, giving Normalization Form C: combine each
+        /// code point with the last preceding starter it isn't blocked from,
+        /// where possible.
+        fn compose_canonically(decomposed: Vec<u32>) -> Vec<u32> {
+            let mut composed: Vec<u32> = Vec::with_capacity(decomposed.len());
+            let mut starter = None;
+            // The combining class of the last code point after `starter`, if
+            // there is one.  Any code point between it and the starter has a
+            // lower combining class.
+            let mut last_class = None;
+            for code in decomposed {
+                let class = canonical_combining_class(code);
+                if let Some(starter) = starter {
+                    let blocked = last_class.map_or(false, |last_class| last_class >= class);
+                    if !blocked {
+                        if let Some(composite) = canonical_composition(composed[starter], code) {
+                            composed[starter] = composite;
+                            continue;
+                        }
+                    }
+                }
+
+                if class == 0 {
+                    starter = Some(composed.len());
+                    last_class = None;
+                } else {
+                    last_class = Some(class);
+                }
+                composed.push(code);
+            }
+
+            composed
+        }
+
+        /// Return `text` converted to Normalization Form C.  Unpaired
+        /// surrogates are preserved.
+        ///
+        /// ```text
+        /// // U+0041, U+030A COMBINING RING ABOVE -> U+00C5
+        /// assert_eq!(to_nfc(&[0x41, 0x30A]), [0xC5]);
+        /// // U+212B ANGSTROM SIGN -> U+00C5
+        /// assert_eq!(to_nfc(&[0x212B]), [0xC5]);
+        /// // U+1100 HANGUL CHOSEONG KIYEOK, U+1161 HANGUL JUNGSEONG A -> U+AC00
+        /// assert_eq!(to_nfc(&[0x1100, 0x1161]), [0xAC00]);
+        /// ```
+        pub fn to_nfc(text: &[u16]) -> Vec<u16> {
+            let mut nfc = Vec::with_capacity(text.len());
+            for code in compose_canonically(decompose_canonically(utf16_code_points(text))) {
+                push_utf16_code_point(&mut nfc, code);
+            }
+            nfc
+        }
+    }
+}
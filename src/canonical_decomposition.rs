@@ -0,0 +1,120 @@
+//! Generate the full canonical decomposition of every code point, and a
+//! function converting UTF-16 text to Normalization Form D.
+
+use crate::code_point_mapping;
+use crate::ucd::UnicodeDataRecord;
+use quote::quote;
+use std::collections::BTreeMap;
+
+/// Compute the full canonical decomposition of every code point that has one,
+/// by recursively applying the canonical decomposition mappings in
+/// UnicodeData.txt.  Hangul syllables are decomposed algorithmically and so
+/// aren't included.
+fn compute_canonical_decompositions(unicode_data: &[UnicodeDataRecord]) -> BTreeMap<u32, Vec<u32>> {
+    let mappings: BTreeMap<u32, &[u32]> = unicode_data
+        .iter()
+        .filter(|record| record.decomposition_tag.is_none() && !record.decomposition.is_empty())
+        .map(|record| (*record.range.start(), record.decomposition.as_slice()))
+        .collect();
+
+    fn decompose(code: u32, mappings: &BTreeMap<u32, &[u32]>, decomposition: &mut Vec<u32>) {
+        match mappings.get(&code) {
+            Some(mapping) => {
+                for code in mapping.iter() {
+                    decompose(*code, mappings, decomposition);
+                }
+            }
+            None => decomposition.push(code),
+        }
+    }
+
+    mappings
+        .keys()
+        .map(|code| {
+            let mut decomposition = vec![];
+            decompose(*code, &mappings, &mut decomposition);
+            (*code, decomposition)
+        })
+        .collect()
+}
+
+/// Generate `canonical_decomposition` and its tables, and `to_nfd`.
+pub fn generate_canonical_decomposition_functions(
+    unicode_data: &[UnicodeDataRecord],
+) -> proc_macro2::TokenStream {
+    let canonical_decomposition_fn = code_point_mapping::generate_code_point_mapping(
+        "canonicaldecomposition",
+        "canonical_decomposition",
+        r#"
+Return the full canonical decomposition of the provided code point, or `None`
+if it decomposes to itself.  Hangul syllables, which are decomposed
+algorithmically, are also `None`; see `decompose_hangul_syllable`.
+
+```text
+// U+212B ANGSTROM SIGN
+assert_eq!(canonical_decomposition(0x212B), Some(&[0x41, 0x30A][..]));
+assert_eq!(canonical_decomposition('a' as u32), None);
+```
+"#
+        .trim(),
+        &compute_canonical_decompositions(unicode_data),
+    );
+
+    quote! {
+        #canonical_decomposition_fn
+
+        /// Return the code points in `code_points` in Normalization Form D:
+        /// fully canonically decomposed, with each run of combining marks
+        /// sorted by Canonical_Combining_Class.
+        fn decompose_canonically(code_points: impl Iterator<Item = u32>) -> Vec<u32> {
+            let mut decomposed = vec![];
+            for code in code_points {
+                if let Some((leading, vowel, trailing)) = decompose_hangul_syllable(code) {
+                    decomposed.push(leading);
+                    decomposed.push(vowel);
+                    decomposed.extend(trailing);
+                } else if let Some(decomposition) = canonical_decomposition(code) {
+                    decomposed.extend_from_slice(decomposition);
+                } else {
+                    decomposed.push(code);
+                }
+            }
+
+            // The canonical ordering algorithm: stably sort each run of code
+            // points with nonzero combining classes.
+            let mut start = 0;
+            while start < decomposed.len() {
+                if canonical_combining_class(decomposed[start]) == 0 {
+                    start += 1;
+                    continue;
+                }
+
+                let end = decomposed[start..]
+                    .iter()
+                    .position(|code| canonical_combining_class(*code) == 0)
+                    .map_or(decomposed.len(), |length| start + length);
+                decomposed[start..end].sort_by_key(|code| canonical_combining_class(*code));
+                start = end;
+            }
+
+            decomposed
+        }
+
+        /// Return `text` converted to Normalization Form D.  Unpaired
+        /// surrogates are preserved.
+        ///
+        /// ```text
+        /// // U+1E0B LATIN SMALL LETTER D WITH DOT ABOVE, U+0323 COMBINING DOT BELOW
+        /// assert_eq!(to_nfd(&[0x1E0B, 0x323]), [0x64, 0x323, 0x307]);
+        /// // U+AC01 HANGUL SYLLABLE GAG
+        /// assert_eq!(to_nfd(&[0xAC01]), [0x1100, 0x1161, 0x11A8]);
+        /// ```
+        pub fn to_nfd(text: &[u16]) -> Vec<u16> {
+            let mut nfd = Vec::with_capacity(text.len());
+            for code in decompose_canonically(utf16_code_points(text)) {
+                push_utf16_code_point(&mut nfd, code);
+            }
+            nfd
+        }
+    }
+}
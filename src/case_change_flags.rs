@@ -0,0 +1,113 @@
+//! Generate a table of the Changes_When_* case properties of every code point,
+//! from DerivedCoreProperties.txt, and functions that test each property.
+
+use crate::code_point_lookup;
+use crate::ucd;
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::quote;
+
+/// Each property: its name in DerivedCoreProperties.txt, the name of the
+/// constant for its bit, the name of the function testing it, and the mapping
+/// that changes code points having it.
+const CASE_CHANGE_PROPERTIES: [(&str, &str, &str, &str); 5] = [
+    (
+        "Changes_When_Lowercased",
+        "CHANGES_WHEN_LOWERCASED",
+        "changes_when_lowercased",
+        "lowercasing (toLowercase)",
+    ),
+    (
+        "Changes_When_Uppercased",
+        "CHANGES_WHEN_UPPERCASED",
+        "changes_when_uppercased",
+        "uppercasing (toUppercase)",
+    ),
+    (
+        "Changes_When_Titlecased",
+        "CHANGES_WHEN_TITLECASED",
+        "changes_when_titlecased",
+        "titlecasing (toTitlecase)",
+    ),
+    (
+        "Changes_When_Casefolded",
+        "CHANGES_WHEN_CASEFOLDED",
+        "changes_when_casefolded",
+        "full case folding (toCasefold) of its NFD form",
+    ),
+    (
+        "Changes_When_Casemapped",
+        "CHANGES_WHEN_CASEMAPPED",
+        "changes_when_casemapped",
+        "lowercasing, uppercasing or titlecasing",
+    ),
+];
+
+/// Compute the case change flags of every code point, as a bit set of
+/// `1 << i` for each `CASE_CHANGE_PROPERTIES[i]` it has.
+fn compute_case_change_flags() -> Vec<u8> {
+    let mut flags = vec![0u8; ucd::CODE_POINT_LIMIT as usize];
+    for (bit, (property, _, _, _)) in CASE_CHANGE_PROPERTIES.iter().enumerate() {
+        let values = ucd::read_binary_property("DerivedCoreProperties.txt", property);
+        for (code_flags, has_property) in flags.iter_mut().zip(values) {
+            if has_property {
+                *code_flags |= 1 << bit;
+            }
+        }
+    }
+
+    flags
+}
+
+/// Generate the `case_change_flags` function and the tables backing it, the
+/// `CHANGES_WHEN_*` bits it returns, and a function testing each bit.
+pub fn generate_case_change_functions() -> proc_macro2::TokenStream {
+    let case_change_flags_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(u8),
+        "casechangeflags",
+        "case_change_flags",
+        r#"
+Return a bit set of the `CHANGES_WHEN_*` case properties of the provided code
+point: `CHANGES_WHEN_LOWERCASED`, `CHANGES_WHEN_UPPERCASED`,
+`CHANGES_WHEN_TITLECASED`, `CHANGES_WHEN_CASEFOLDED` and
+`CHANGES_WHEN_CASEMAPPED`.  A string whose code points all lack a property is
+unchanged by the corresponding mapping.
+"#
+        .trim(),
+        &compute_case_change_flags(),
+    );
+
+    let property_functions = CASE_CHANGE_PROPERTIES.iter().enumerate().map(
+        |(bit, (property, flag_name, fn_name, mapping))| {
+            let mask = 1u8 << bit;
+            let flag_doc = format!(
+                "The bit set by `case_change_flags` for a code point with the {} property.",
+                property
+            );
+            let fn_doc = format!(
+                "Return true iff the provided code point has the {} property: that is, {} \
+                 changes it.",
+                property, mapping
+            );
+            let flag_name = Ident::new(flag_name, Span::call_site());
+            let fn_name = Ident::new(fn_name, Span::call_site());
+
+            quote! {
+                #[doc = #flag_doc]
+                pub const #flag_name: u8 = #mask;
+
+                #[doc = #fn_doc]
+                #[inline]
+                pub fn #fn_name(code: u32) -> bool {
+                    case_change_flags(code) & #flag_name != 0
+                }
+            }
+        },
+    );
+
+    quote! {
+        #case_change_flags_fn
+
+        #( #property_functions )*
+    }
+}
@@ -0,0 +1,193 @@
+//! Generate functions that uppercase and lowercase UTF-16 text, as
+//! String.prototype.toUpperCase and String.prototype.toLowerCase do, from the
+//! BMP, non-BMP and special casing primitives.
+
+use crate::code_point_lookup;
+use crate::ucd;
+use quote::quote;
+
+/// The bit set by `case_context_flags` for a Cased code point.
+const CASED: u8 = 1;
+
+/// The bit set by `case_context_flags` for a Case_Ignorable code point.
+const CASE_IGNORABLE: u8 = 2;
+
+/// Compute the Cased and Case_Ignorable properties of every code point, which
+/// determine the context of a Final_Sigma.
+fn compute_case_context_flags() -> Vec<u8> {
+    let cased = ucd::read_binary_property("DerivedCoreProperties.txt", "Cased");
+    let case_ignorable = ucd::read_binary_property("DerivedCoreProperties.txt", "Case_Ignorable");
+
+    cased
+        .into_iter()
+        .zip(case_ignorable)
+        .map(|(cased, case_ignorable)| {
+            let mut flags = 0;
+            if cased {
+                flags |= CASED;
+            }
+            if case_ignorable {
+                flags |= CASE_IGNORABLE;
+            }
+            flags
+        })
+        .collect()
+}
+
+/// Generate `to_upper_case_utf16` and `to_lower_case_utf16`, and the private
+/// `case_context_flags` lookup and `is_final_sigma` function they use.
+pub fn generate_case_conversion_functions() -> proc_macro2::TokenStream {
+    let case_context_flags_fn = code_point_lookup::generate_private_code_point_lookup(
+        quote!(u8),
+        "casecontextflags",
+        "case_context_flags",
+        &format!(
+            "Return {} if the provided code point is Cased, plus {} if it's Case_Ignorable.",
+            CASED, CASE_IGNORABLE
+        ),
+        &compute_case_context_flags(),
+    );
+
+    quote! {
+        #case_context_flags_fn
+
+        /// Return true iff the GREEK CAPITAL LETTER SIGMA at `text[index]` is
+        /// in the Final_Sigma context of SpecialCasing.txt: preceded by a
+        /// Cased code point and not followed by one, ignoring Case_Ignorable
+        /// code points in between.
+        fn is_final_sigma(text: &[u16], index: usize) -> bool {
+            // Case_Ignorable code points are skipped even if they're also
+            // Cased.
+            let mut before = index;
+            let mut preceded_by_cased = false;
+            while before > 0 {
+                let (code, length) = decode_utf16_code_point_before(text, before);
+                before -= length;
+                let flags = case_context_flags(code);
+                if flags & #CASE_IGNORABLE == 0 {
+                    preceded_by_cased = flags & #CASED != 0;
+                    break;
+                }
+            }
+            if !preceded_by_cased {
+                return false;
+            }
+
+            let mut after = index + 1;
+            while after < text.len() {
+                let (code, length) = decode_utf16_code_point(text, after);
+                after += length;
+                let flags = case_context_flags(code);
+                if flags & #CASE_IGNORABLE == 0 {
+                    return flags & #CASED == 0;
+                }
+            }
+            true
+        }
+
+        /// Append the full uppercase mapping of `text` to `output`, as
+        /// String.prototype.toUpperCase does.  Surrogate pairs are uppercased
+        /// as the code point they encode; unpaired surrogates are copied
+        /// unchanged.
+        ///
+        /// ```text
+        /// // "Straße" -> "STRASSE"
+        /// let mut output = vec![];
+        /// to_upper_case_utf16(&[0x53, 0x74, 0x72, 0x61, 0xDF, 0x65], &mut output);
+        /// assert_eq!(output, [0x53, 0x54, 0x52, 0x41, 0x53, 0x53, 0x45]);
+        /// ```
+        pub fn to_upper_case_utf16(text: &[u16], output: &mut Vec<u16>) {
+            // No code unit of a surrogate pair has a special casing mapping, so
+            // every unit without one uppercases to a single unit.
+            let length: usize = text
+                .iter()
+                .map(|unit| {
+                    if changes_when_upper_cased_special_casing(*unit) {
+                        length_upper_case_special_casing(*unit)
+                    } else {
+                        1
+                    }
+                })
+                .sum();
+            output.reserve(length);
+
+            let elements = output.as_mut_ptr();
+            let mut written = output.len();
+            let mut index = 0;
+            while index < text.len() {
+                let unit = text[index];
+                if (0xD800..=0xDBFF).contains(&unit)
+                    && index + 1 < text.len()
+                    && (0xDC00..=0xDFFF).contains(&text[index + 1])
+                {
+                    let trail = to_upper_case_non_bmp_trail(unit, text[index + 1]);
+                    // SAFETY: `length` units were reserved past the original
+                    // length, and the prepass counted both of these.
+                    unsafe {
+                        elements.add(written).write(unit);
+                        elements.add(written + 1).write(trail);
+                    }
+                    written += 2;
+                    index += 2;
+                    continue;
+                }
+
+                if changes_when_upper_cased_special_casing(unit) {
+                    // SAFETY: the prepass counted the units this appends.
+                    unsafe {
+                        append_upper_case_special_casing(unit, elements, &mut written);
+                    }
+                } else {
+                    let upper = u16::wrapping_add(unit, char_info(unit).upper_delta.0);
+                    // SAFETY: the prepass counted this unit.
+                    unsafe {
+                        elements.add(written).write(upper);
+                    }
+                    written += 1;
+                }
+                index += 1;
+            }
+
+            // SAFETY: every unit up to `written` has been initialized.
+            unsafe {
+                output.set_len(written);
+            }
+        }
+
+        /// Append the full lowercase mapping of `text` to `output`, as
+        /// String.prototype.toLowerCase does, including the Final_Sigma
+        /// context.  Surrogate pairs are lowercased as the code point they
+        /// encode; unpaired surrogates are copied unchanged.
+        ///
+        /// ```text
+        /// // "ΟΔΟΣ" -> "οδος"
+        /// let mut output = vec![];
+        /// to_lower_case_utf16(&[0x39F, 0x394, 0x39F, 0x3A3], &mut output);
+        /// assert_eq!(output, [0x3BF, 0x3B4, 0x3BF, 0x3C2]);
+        /// ```
+        pub fn to_lower_case_utf16(text: &[u16], output: &mut Vec<u16>) {
+            const LATIN_CAPITAL_LETTER_I_WITH_DOT_ABOVE: u32 = 0x130;
+            const GREEK_CAPITAL_LETTER_SIGMA: u32 = 0x3A3;
+            const GREEK_SMALL_LETTER_FINAL_SIGMA: u16 = 0x3C2;
+
+            output.reserve(text.len());
+
+            let mut index = 0;
+            while index < text.len() {
+                let (code, length) = decode_utf16_code_point(text, index);
+                match code {
+                    // The only code point whose unconditional lowercase mapping
+                    // is more than one code point.
+                    LATIN_CAPITAL_LETTER_I_WITH_DOT_ABOVE => {
+                        output.extend_from_slice(&[0x69, 0x307]);
+                    }
+                    GREEK_CAPITAL_LETTER_SIGMA if is_final_sigma(text, index) => {
+                        output.push(GREEK_SMALL_LETTER_FINAL_SIGMA);
+                    }
+                    _ => push_utf16_code_point(output, to_lower_case(code)),
+                }
+                index += length;
+            }
+        }
+    }
+}
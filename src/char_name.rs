@@ -0,0 +1,300 @@
+//! Generate tables of the Name property of code points, and functions that
+//! return the name of a code point and the code point with a given name or
+//! name alias.
+//!
+//! Names are stored as sequences of indexes into a dictionary of the words
+//! (space-separated parts) appearing in names.  The names of CJK unified
+//! ideographs, Tangut ideographs and Hangul syllables are computed
+//! algorithmically and aren't stored.
+
+use crate::generate_table;
+use crate::ucd;
+use crate::ucd::UnicodeDataRecord;
+use quote::quote;
+use std::collections::BTreeMap;
+
+/// The bit set in an element of `name_word_indexes` that ends a name.
+const NAME_LAST_WORD: u16 = 0x8000;
+
+/// The prefix of the algorithmic name of each code point in an ideograph range
+/// in UnicodeData.txt, followed in the name by the code point in hex.
+const IDEOGRAPH_NAME_PREFIXES: [(&str, &str); 2] = [
+    ("<CJK Ideograph", "CJK UNIFIED IDEOGRAPH-"),
+    ("<Tangut Ideograph", "TANGUT IDEOGRAPH-"),
+];
+
+/// Read NameAliases.txt, returning every alias and the code point it names,
+/// sorted by alias.
+fn read_name_aliases() -> Vec<(String, u32)> {
+    let contents = ucd::read_data_file("NameAliases.txt");
+
+    let mut aliases = BTreeMap::new();
+    for fields in ucd::data_lines(&contents) {
+        let code = ucd::parse_code_point(fields[0]);
+        let previous = aliases.insert(fields[1].to_string(), code);
+        assert!(previous.is_none(), "duplicate name alias {:?}", fields[1]);
+    }
+
+    aliases.into_iter().collect()
+}
+
+/// Generate the name tables, and the `char_name` and `lookup_by_name`
+/// functions.
+pub fn generate_char_name_functions(
+    unicode_data: &[UnicodeDataRecord],
+) -> proc_macro2::TokenStream {
+    // Code points whose names are listed individually, and their names.
+    let listed: Vec<(u32, &str)> = unicode_data
+        .iter()
+        .filter(|record| !record.name.starts_with('<'))
+        .map(|record| (*record.range.start(), record.name.as_str()))
+        .collect();
+    assert!(listed.len() <= u16::MAX as usize + 1, "too many names");
+
+    let mut word_indexes = BTreeMap::new();
+    for (_, name) in &listed {
+        for word in name.split(' ') {
+            word_indexes.entry(word).or_insert(0u16);
+        }
+    }
+    assert!(
+        word_indexes.len() < NAME_LAST_WORD as usize,
+        "too many words"
+    );
+    for (index, word_index) in word_indexes.values_mut().enumerate() {
+        *word_index = index as u16;
+    }
+    let words: Vec<&str> = word_indexes.keys().copied().collect();
+
+    let mut name_word_indexes = vec![];
+    let mut name_codes = vec![];
+    let mut name_offsets = vec![];
+    for (code, name) in &listed {
+        name_codes.push(*code);
+        name_offsets.push(name_word_indexes.len() as u32);
+        name_word_indexes.extend(name.split(' ').map(|word| word_indexes[word]));
+        *name_word_indexes.last_mut().expect("empty name") |= NAME_LAST_WORD;
+    }
+
+    let mut names_sorted: Vec<u16> = (0..listed.len()).map(|entry| entry as u16).collect();
+    names_sorted.sort_by_key(|entry| listed[*entry as usize].1);
+
+    let ideograph_ranges: Vec<proc_macro2::TokenStream> = unicode_data
+        .iter()
+        .filter_map(|record| {
+            let (_, prefix) = IDEOGRAPH_NAME_PREFIXES
+                .iter()
+                .find(|(range_name, _)| record.name.starts_with(range_name))?;
+            let (start, end) = (record.range.start(), record.range.end());
+            Some(quote!((#start, #end, #prefix)))
+        })
+        .collect();
+
+    let aliases: Vec<proc_macro2::TokenStream> = read_name_aliases()
+        .iter()
+        .map(|(alias, code)| quote!((#alias, #code)))
+        .collect();
+
+    let name_words_table = generate_table::generate_table(
+        quote!(&str),
+        "name_words",
+        "The distinct words in the names of code points, in sorted order.",
+        &words,
+    );
+
+    let name_word_indexes_table = generate_table::generate_table(
+        quote!(u16),
+        "name_word_indexes",
+        r#"
+The names of code points, each a sequence of indexes into `name_words` of its
+words.  The last index in each name has the `NAME_LAST_WORD` bit set.
+"#
+        .trim(),
+        &name_word_indexes,
+    );
+
+    let name_codes_table = generate_table::generate_table(
+        quote!(u32),
+        "name_codes",
+        "The code points whose names are stored in `name_word_indexes`, in ascending order.",
+        &name_codes,
+    );
+
+    let name_offsets_table = generate_table::generate_table(
+        quote!(u32),
+        "name_offsets",
+        "The index in `name_word_indexes` of the name of each code point in `name_codes`.",
+        &name_offsets,
+    );
+
+    let names_sorted_table = generate_table::generate_table(
+        quote!(u16),
+        "names_sorted",
+        "Indexes into `name_codes`, ordered so that the corresponding names are sorted.",
+        &names_sorted,
+    );
+
+    let ideograph_ranges_table = generate_table::generate_table(
+        quote!((u32, u32, &str)),
+        "name_ideograph_ranges",
+        r#"
+Ranges of ideographs whose names are the given prefix followed by the code
+point in hex, e.g. "CJK UNIFIED IDEOGRAPH-4E00".
+"#
+        .trim(),
+        &ideograph_ranges,
+    );
+
+    let aliases_table = generate_table::generate_table(
+        quote!((&str, u32)),
+        "name_aliases",
+        "Every name alias in NameAliases.txt and the code point it names, sorted by alias.",
+        &aliases,
+    );
+
+    quote! {
+        #name_words_table
+
+        /// The bit set in an element of `name_word_indexes` that ends a name.
+        const NAME_LAST_WORD: u16 = #NAME_LAST_WORD;
+
+        #name_word_indexes_table
+
+        #name_codes_table
+
+        #name_offsets_table
+
+        #names_sorted_table
+
+        #ideograph_ranges_table
+
+        #aliases_table
+
+        /// The Jamo_Short_Name of each leading, vowel and trailing jamo, in
+        /// order, as used in the names of Hangul syllables.
+        static hangul_leading_short_names: [&str; HANGUL_LEADING_COUNT as usize] = [
+            "G", "GG", "N", "D", "DD", "R", "M", "B", "BB", "S", "SS", "", "J", "JJ", "C", "K", "T",
+            "P", "H",
+        ];
+        static hangul_vowel_short_names: [&str; HANGUL_VOWEL_COUNT as usize] = [
+            "A", "AE", "YA", "YAE", "EO", "E", "YEO", "YE", "O", "WA", "WAE", "OE", "YO", "U",
+            "WEO", "WE", "WI", "YU", "EU", "YI", "I",
+        ];
+        static hangul_trailing_short_names: [&str; HANGUL_TRAILING_COUNT as usize] = [
+            "", "G", "GG", "GS", "N", "NJ", "NH", "D", "L", "LG", "LM", "LB", "LS", "LT", "LP",
+            "LH", "M", "B", "BS", "S", "SS", "NG", "J", "C", "K", "T", "P", "H",
+        ];
+
+        /// The prefix of the name of every Hangul syllable.
+        const HANGUL_SYLLABLE_PREFIX: &str = "HANGUL SYLLABLE ";
+
+        /// Return the name stored at index `entry` of `name_codes`.
+        fn listed_name(entry: usize) -> String {
+            let mut name = String::new();
+            let mut offset = name_offsets[entry] as usize;
+            loop {
+                let word = name_word_indexes[offset];
+                if !name.is_empty() {
+                    name.push(' ');
+                }
+                name.push_str(name_words[(word & !NAME_LAST_WORD) as usize]);
+                if word & NAME_LAST_WORD != 0 {
+                    return name;
+                }
+                offset += 1;
+            }
+        }
+
+        /// Return the Name property of the provided code point, or `None` if
+        /// it has no name: for example, if it's unassigned, a control, a
+        /// surrogate or a private-use code point.  (Name aliases, such as
+        /// "LINE FEED", aren't returned.)
+        ///
+        /// ```text
+        /// assert_eq!(char_name(0xDF).as_deref(), Some("LATIN SMALL LETTER SHARP S"));
+        /// assert_eq!(char_name(0x4E00).as_deref(), Some("CJK UNIFIED IDEOGRAPH-4E00"));
+        /// assert_eq!(char_name(0xAC00).as_deref(), Some("HANGUL SYLLABLE GA"));
+        /// assert_eq!(char_name(0x0A), None);
+        /// ```
+        pub fn char_name(code: u32) -> Option<String> {
+            if let Ok(entry) = name_codes.binary_search(&code) {
+                return Some(listed_name(entry));
+            }
+
+            if let Some((leading, vowel, trailing)) = decompose_hangul_syllable(code) {
+                let trailing = trailing.map_or(0, |trailing| trailing - HANGUL_TRAILING_BASE);
+                return Some(format!(
+                    "{}{}{}{}",
+                    HANGUL_SYLLABLE_PREFIX,
+                    hangul_leading_short_names[(leading - HANGUL_LEADING_BASE) as usize],
+                    hangul_vowel_short_names[(vowel - HANGUL_VOWEL_BASE) as usize],
+                    hangul_trailing_short_names[trailing as usize],
+                ));
+            }
+
+            name_ideograph_ranges
+                .iter()
+                .find(|(start, end, _)| (*start..=*end).contains(&code))
+                .map(|(_, _, prefix)| format!("{}{:04X}", prefix, code))
+        }
+
+        /// Return the code point whose name is `name`, if any.  Name aliases
+        /// from NameAliases.txt, such as "LINE FEED" and "BOM", are also
+        /// recognized.  Names are matched ignoring ASCII case.
+        ///
+        /// ```text
+        /// assert_eq!(lookup_by_name("LATIN SMALL LETTER SHARP S"), Some(0xDF));
+        /// assert_eq!(lookup_by_name("line feed"), Some(0x0A));
+        /// assert_eq!(lookup_by_name("HANGUL SYLLABLE GA"), Some(0xAC00));
+        /// assert_eq!(lookup_by_name("NOT A NAME"), None);
+        /// ```
+        pub fn lookup_by_name(name: &str) -> Option<u32> {
+            let name = name.to_ascii_uppercase();
+
+            if let Ok(index) =
+                names_sorted.binary_search_by(|entry| listed_name(*entry as usize).cmp(&name))
+            {
+                return Some(name_codes[names_sorted[index] as usize]);
+            }
+
+            if let Ok(index) = name_aliases.binary_search_by(|(alias, _)| alias.cmp(&name.as_str())) {
+                return Some(name_aliases[index].1);
+            }
+
+            if let Some(syllable) = name.strip_prefix(HANGUL_SYLLABLE_PREFIX) {
+                for (leading, leading_name) in hangul_leading_short_names.iter().enumerate() {
+                    let rest = match syllable.strip_prefix(leading_name) {
+                        Some(rest) => rest,
+                        None => continue,
+                    };
+                    for (vowel, vowel_name) in hangul_vowel_short_names.iter().enumerate() {
+                        let rest = match rest.strip_prefix(vowel_name) {
+                            Some(rest) => rest,
+                            None => continue,
+                        };
+                        if let Some(trailing) =
+                            hangul_trailing_short_names.iter().position(|t| *t == rest)
+                        {
+                            let syllable = compose_hangul(
+                                HANGUL_LEADING_BASE + leading as u32,
+                                HANGUL_VOWEL_BASE + vowel as u32,
+                            )?;
+                            return Some(syllable + trailing as u32);
+                        }
+                    }
+                }
+                return None;
+            }
+
+            name_ideograph_ranges.iter().find_map(|(start, end, prefix)| {
+                let code = u32::from_str_radix(name.strip_prefix(prefix)?, 16).ok()?;
+                let canonical = format!("{}{:04X}", prefix, code);
+                if (*start..=*end).contains(&code) && canonical == name {
+                    Some(code)
+                } else {
+                    None
+                }
+            })
+        }
+    }
+}
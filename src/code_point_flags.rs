@@ -0,0 +1,120 @@
+//! Generate a table of flags classifying every code point as default-ignorable,
+//! a noncharacter, private-use, a surrogate or unassigned, and functions that
+//! test each flag.
+
+use crate::code_point_lookup;
+use crate::ucd;
+use crate::ucd::UnicodeDataRecord;
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::quote;
+
+/// Each flag: the name of the constant for its bit, the name of the function
+/// testing it, and that function's documentation.
+const CODE_POINT_FLAGS: [(&str, &str, &str); 5] = [
+    (
+        "CODE_POINT_FLAG_DEFAULT_IGNORABLE",
+        "is_default_ignorable",
+        "has the Default_Ignorable_Code_Point property, so that it should be invisible when \
+         not supported",
+    ),
+    (
+        "CODE_POINT_FLAG_NONCHARACTER",
+        "is_noncharacter",
+        "is a noncharacter (Noncharacter_Code_Point), such as U+FFFE or U+FDD0",
+    ),
+    (
+        "CODE_POINT_FLAG_PRIVATE_USE",
+        "is_private_use",
+        "is a private-use code point (General_Category=Co)",
+    ),
+    (
+        "CODE_POINT_FLAG_SURROGATE",
+        "is_surrogate",
+        "is a surrogate code point (General_Category=Cs)",
+    ),
+    (
+        "CODE_POINT_FLAG_UNASSIGNED",
+        "is_unassigned",
+        "is unassigned (General_Category=Cn), including noncharacters",
+    ),
+];
+
+/// Compute the flags of every code point, as a bit set of
+/// `1 << i` for each `CODE_POINT_FLAGS[i]` that applies.
+fn compute_code_point_flags(unicode_data: &[UnicodeDataRecord]) -> Vec<u8> {
+    let default_ignorable =
+        ucd::read_binary_property("DerivedCoreProperties.txt", "Default_Ignorable_Code_Point");
+    let noncharacter = ucd::read_binary_property("PropList.txt", "Noncharacter_Code_Point");
+    let general_categories = ucd::general_categories(unicode_data);
+
+    (0..ucd::CODE_POINT_LIMIT as usize)
+        .map(|code| {
+            let category = general_categories[code];
+            [
+                default_ignorable[code],
+                noncharacter[code],
+                category == "Co",
+                category == "Cs",
+                category == "Cn",
+            ]
+            .iter()
+            .enumerate()
+            .filter(|(_, has_flag)| **has_flag)
+            .fold(0, |flags, (bit, _)| flags | 1 << bit)
+        })
+        .collect()
+}
+
+/// Generate the `code_point_flags` function and the tables backing it, the
+/// `CODE_POINT_FLAG_*` bits it returns, and a function testing each bit.
+pub fn generate_code_point_flags_functions(
+    unicode_data: &[UnicodeDataRecord],
+) -> proc_macro2::TokenStream {
+    let code_point_flags_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(u8),
+        "codepointflags",
+        "code_point_flags",
+        r#"
+Return a bit set of the `CODE_POINT_FLAG_*` flags that apply to the provided
+code point: `CODE_POINT_FLAG_DEFAULT_IGNORABLE`, `CODE_POINT_FLAG_NONCHARACTER`,
+`CODE_POINT_FLAG_PRIVATE_USE`, `CODE_POINT_FLAG_SURROGATE` and
+`CODE_POINT_FLAG_UNASSIGNED`.  A code point with no flags set is assigned,
+visible, and not reserved for private or internal use.
+"#
+        .trim(),
+        &compute_code_point_flags(unicode_data),
+    );
+
+    let flag_functions =
+        CODE_POINT_FLAGS
+            .iter()
+            .enumerate()
+            .map(|(bit, (flag_name, fn_name, description))| {
+                let mask = 1u8 << bit;
+                let flag_doc = format!(
+                    "The bit set by `code_point_flags` for a code point that {}.",
+                    description
+                );
+                let fn_doc = format!("Return true iff the provided code point {}.", description);
+                let flag_name = Ident::new(flag_name, Span::call_site());
+                let fn_name = Ident::new(fn_name, Span::call_site());
+
+                quote! {
+                    #[doc = #flag_doc]
+                    pub const #flag_name: u8 = #mask;
+
+                    #[doc = #fn_doc]
+                    #[inline]
+                    pub fn #fn_name(code: u32) -> bool {
+                        code_point_flags(code) & #flag_name != 0
+                    }
+                }
+            });
+
+    quote! {
+        #code_point_flags_fn
+
+        #( #flag_functions )*
+    }
+}
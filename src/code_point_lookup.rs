@@ -0,0 +1,157 @@
+//! Generate a compressed table storing a value for every code point, together
+//! with a function that looks up the value for a code point.
+
+use crate::generate_table;
+use crate::index_table;
+use crate::ucd::CODE_POINT_LIMIT;
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::format_ident;
+use quote::quote;
+use std::collections::BTreeMap;
+use unicode_info::table;
+
+/// An unassigned code point in an unallocated plane, whose value lookup
+/// functions return for values that aren't code points, rather than indexing
+/// outside their tables.  (Panicking isn't an option in an `extern "C"`
+/// function.)
+const OUT_OF_RANGE_CODE_POINT: u32 = 0x5_0000;
+
+/// Generate code to look up a value, of type `value_type`, for every code
+/// point.  `values[code]` is the value for the code point `code`.
+///
+/// The distinct values are stored in a table named `table_name`, and the index
+/// of the value for each code point is stored split across index tables named
+/// `{table_name}_index1` and `{table_name}_index2`, exactly as for `charinfo`.
+/// The shift used to index into them is `{TABLE_NAME}_SHIFT`.
+///
+/// A function named `fn_name`, documented by `doc`, accepts a code point and
+/// returns its value.  Called with a value that isn't a code point, i.e. one
+/// greater than 0x10FFFF, it returns the value of `OUT_OF_RANGE_CODE_POINT`.
+pub fn generate_code_point_lookup<T>(
+    value_type: proc_macro2::TokenStream,
+    table_name: &str,
+    fn_name: &str,
+    doc: &str,
+    values: &[T],
+) -> proc_macro2::TokenStream
+where
+    T: Clone + Ord + quote::ToTokens,
+{
+    generate_lookup(value_type, table_name, fn_name, doc, values, true)
+}
+
+/// Generate code exactly as `generate_code_point_lookup` does, except that the
+/// lookup function is private and isn't `extern "C"`.  This suits lookups that
+/// only back other generated functions.
+pub fn generate_private_code_point_lookup<T>(
+    value_type: proc_macro2::TokenStream,
+    table_name: &str,
+    fn_name: &str,
+    doc: &str,
+    values: &[T],
+) -> proc_macro2::TokenStream
+where
+    T: Clone + Ord + quote::ToTokens,
+{
+    generate_lookup(value_type, table_name, fn_name, doc, values, false)
+}
+
+fn generate_lookup<T>(
+    value_type: proc_macro2::TokenStream,
+    table_name: &str,
+    fn_name: &str,
+    doc: &str,
+    values: &[T],
+    public: bool,
+) -> proc_macro2::TokenStream
+where
+    T: Clone + Ord + quote::ToTokens,
+{
+    assert_eq!(values.len(), CODE_POINT_LIMIT as usize);
+
+    // Assign each distinct value an index into the table of values.
+    let mut value_indexes = BTreeMap::new();
+    for value in values {
+        let next_index = value_indexes.len() as u32;
+        value_indexes.entry(value.clone()).or_insert(next_index);
+    }
+
+    let mut table = vec![None; value_indexes.len()];
+    for (value, index) in &value_indexes {
+        table[*index as usize] = Some(value.clone());
+    }
+    let table: Vec<T> = table.into_iter().map(Option::unwrap).collect();
+
+    let index: Vec<u32> = values.iter().map(|value| value_indexes[value]).collect();
+    let out_of_range_index = index[OUT_OF_RANGE_CODE_POINT as usize] as usize;
+
+    let table::TableSplit {
+        index1,
+        index1_elem_type,
+        index2,
+        index2_elem_type,
+        shift,
+    } = table::split_table(&index);
+
+    let index1_name = format!("{}_index1", table_name);
+    let index2_name = format!("{}_index2", table_name);
+    let shift_name = format_ident!("{}_SHIFT", table_name.to_uppercase());
+
+    let table_doc = format!(
+        "The table of distinct values returned by `{}`, into which `{}` and `{}` index.",
+        fn_name, index1_name, index2_name
+    );
+    let value_table =
+        generate_table::generate_table(value_type.clone(), table_name, &table_doc, &table);
+
+    let index_tables = index_table::generate_index_tables(
+        &index1,
+        index1_elem_type,
+        &index1_name,
+        &index2,
+        index2_elem_type,
+        &index2_name,
+    );
+
+    let table_name = Ident::new(table_name, Span::call_site());
+    let index1_name = Ident::new(&index1_name, Span::call_site());
+    let index2_name = Ident::new(&index2_name, Span::call_site());
+    let fn_name = Ident::new(fn_name, Span::call_site());
+
+    let signature = if public {
+        quote! {
+            #[no_mangle]
+            #[inline]
+            pub extern "C" fn #fn_name(code: u32) -> #value_type
+        }
+    } else {
+        quote! {
+            #[inline]
+            fn #fn_name(code: u32) -> #value_type
+        }
+    };
+
+    quote! {
+        #value_table
+
+        /// The shift used in indexing into the two index tables.
+        #[no_mangle]
+        const #shift_name: u32 = #shift;
+
+        #index_tables
+
+        #[doc = #doc]
+        #signature {
+            if code > 0x10FFFF {
+                return #table_name[#out_of_range_index];
+            }
+
+            let mask = (1usize << #shift_name) - 1;
+            let index1_entry = #index1_name[(code >> #shift_name) as usize] as usize;
+            let index2_entry =
+                #index2_name[(index1_entry << #shift_name) + (code as usize & mask)];
+            #table_name[index2_entry as usize]
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Generate compact tables mapping code points to sequences of code points,
+//! together with a function that looks up the sequence a code point maps to.
+
+use crate::code_point_lookup;
+use crate::generate_table;
+use crate::ucd::CODE_POINT_LIMIT;
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::format_ident;
+use quote::quote;
+use std::collections::BTreeMap;
+
+/// The number of low bits of a lookup entry storing the length of the mapped
+/// sequence.  The remaining bits store its offset.
+const LENGTH_BITS: u32 = 5;
+
+/// Generate code to look up the sequence of code points that each code point in
+/// `mappings` maps to.
+///
+/// Each distinct sequence is stored once, in a table named `{table_name}_data`.
+/// For every code point, the offset and length of its sequence (or `u32::MAX`
+/// if it isn't mapped) is stored in a table named `table_name`, as by
+/// `generate_code_point_lookup`.  A code point may map to the empty sequence.
+///
+/// A function named `fn_name`, documented by `doc`, accepts a code point and
+/// returns `Some(sequence)` if it's mapped, or `None` otherwise.
+pub fn generate_code_point_mapping(
+    table_name: &str,
+    fn_name: &str,
+    doc: &str,
+    mappings: &BTreeMap<u32, Vec<u32>>,
+) -> proc_macro2::TokenStream {
+    let mut data = vec![];
+    let mut offsets = BTreeMap::new();
+    let mut entries = vec![u32::MAX; CODE_POINT_LIMIT as usize];
+    for (code, sequence) in mappings {
+        assert!(
+            sequence.len() < 1 << LENGTH_BITS,
+            "bad mapping length for {:04X} in {}",
+            code,
+            table_name
+        );
+
+        let offset = *offsets.entry(sequence.clone()).or_insert_with(|| {
+            let offset = data.len() as u32;
+            data.extend_from_slice(sequence);
+            offset
+        });
+        entries[*code as usize] = offset << LENGTH_BITS | sequence.len() as u32;
+    }
+    assert!(
+        data.len() < (1 << (32 - LENGTH_BITS)) - 1,
+        "too much data in {}",
+        table_name
+    );
+
+    let data_name = format!("{}_data", table_name);
+    let entry_fn_name = format!("{}_entry", fn_name);
+
+    let data_doc = format!(
+        "The distinct sequences returned by `{}`, one after another.",
+        fn_name
+    );
+    let data_table = generate_table::generate_table(quote!(u32), &data_name, &data_doc, &data);
+
+    let entry_doc = format!(
+        "Return the offset in `{}`, shifted left by {}, and length of the sequence the \
+         provided code point maps to, or `u32::MAX` if it isn't mapped.",
+        data_name, LENGTH_BITS
+    );
+    let entry_fn = code_point_lookup::generate_private_code_point_lookup(
+        quote!(u32),
+        table_name,
+        &entry_fn_name,
+        &entry_doc,
+        &entries,
+    );
+
+    let data_name = Ident::new(&data_name, Span::call_site());
+    let entry_fn_name = Ident::new(&entry_fn_name, Span::call_site());
+    let fn_name = Ident::new(fn_name, Span::call_site());
+    let length_mask = (1u32 << LENGTH_BITS) - 1;
+    let length_bits_name = format_ident!("{}_LENGTH_BITS", table_name.to_uppercase());
+
+    quote! {
+        #data_table
+
+        /// The number of low bits of an entry in the lookup table storing the
+        /// length of a sequence.
+        const #length_bits_name: u32 = #LENGTH_BITS;
+
+        #entry_fn
+
+        #[doc = #doc]
+        #[inline]
+        pub fn #fn_name(code: u32) -> Option<&'static [u32]> {
+            let entry = #entry_fn_name(code);
+            if entry == u32::MAX {
+                return None;
+            }
+
+            let offset = (entry >> #length_bits_name) as usize;
+            let length = (entry & #length_mask) as usize;
+            Some(&#data_name[offset..offset + length])
+        }
+    }
+}
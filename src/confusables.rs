@@ -0,0 +1,80 @@
+//! Generate the prototype mapping in confusables.txt, and the skeleton
+//! function defined by UTS #39, Unicode Security Mechanisms, for detecting
+//! confusable strings.
+
+use crate::code_point_mapping;
+use crate::ucd;
+use quote::quote;
+use std::collections::BTreeMap;
+
+/// Read confusables.txt, returning the prototype each listed code point maps
+/// to.
+fn read_confusable_prototypes() -> BTreeMap<u32, Vec<u32>> {
+    let contents = ucd::read_data_file("confusables.txt");
+
+    let mut prototypes = BTreeMap::new();
+    for fields in ucd::data_lines(&contents) {
+        assert_eq!(
+            fields[2], "MA",
+            "unexpected confusable type {:?}",
+            fields[2]
+        );
+        let code = ucd::parse_code_point(fields[0]);
+        let previous = prototypes.insert(code, ucd::parse_code_point_sequence(fields[1]));
+        assert!(previous.is_none(), "duplicate confusable {:04X}", code);
+    }
+
+    prototypes
+}
+
+/// Generate `confusable_prototype` and its tables, and `skeleton`.
+pub fn generate_confusables_functions() -> proc_macro2::TokenStream {
+    let confusable_prototype_fn = code_point_mapping::generate_code_point_mapping(
+        "confusableprototype",
+        "confusable_prototype",
+        r#"
+Return the prototype that the provided code point is visually confusable with,
+as listed in confusables.txt, or `None` if it's its own prototype.
+
+```text
+// U+0430 CYRILLIC SMALL LETTER A
+assert_eq!(confusable_prototype(0x0430), Some(&[0x61][..]));
+assert_eq!(confusable_prototype('a' as u32), None);
+```
+"#
+        .trim(),
+        &read_confusable_prototypes(),
+    );
+
+    quote! {
+        #confusable_prototype_fn
+
+        /// Return the skeleton of `text`, as defined by UTS #39 section 4: its
+        /// NFD form with default-ignorable code points removed and every
+        /// remaining code point replaced by its prototype, converted to NFD
+        /// again.  Two strings are confusable iff their skeletons are equal.
+        ///
+        /// ```text
+        /// // U+0440 CYRILLIC SMALL LETTER ER, U+0430 CYRILLIC SMALL LETTER A
+        /// assert_eq!(skeleton(&[0x440, 0x430]), skeleton(&[0x70, 0x61]));
+        /// ```
+        pub fn skeleton(text: &[u16]) -> Vec<u16> {
+            let mut mapped = vec![];
+            for code in decompose_canonically(utf16_code_points(text)) {
+                if is_default_ignorable(code) {
+                    continue;
+                }
+                match confusable_prototype(code) {
+                    Some(prototype) => mapped.extend_from_slice(prototype),
+                    None => mapped.push(code),
+                }
+            }
+
+            let mut skeleton = Vec::with_capacity(mapped.len());
+            for code in decompose_canonically(mapped.into_iter()) {
+                push_utf16_code_point(&mut skeleton, code);
+            }
+            skeleton
+        }
+    }
+}
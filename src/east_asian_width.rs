@@ -0,0 +1,119 @@
+//! Compute the East_Asian_Width property, and generate functions returning it
+//! and the display width of code points in a terminal.
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::ucd;
+use crate::ucd::UnicodeDataRecord;
+use quote::quote;
+
+pub const EAST_ASIAN_WIDTH: &str = "EastAsianWidth";
+
+/// The `EastAsianWidth` variant for each value in EastAsianWidth.txt.
+pub const EAST_ASIAN_WIDTH_VALUES: [(&str, &str); 6] = [
+    ("N", "Neutral"),
+    ("A", "Ambiguous"),
+    ("H", "Halfwidth"),
+    ("F", "Fullwidth"),
+    ("Na", "Narrow"),
+    ("W", "Wide"),
+];
+
+/// Compute the East_Asian_Width of every code point.
+pub fn compute_east_asian_widths() -> Vec<PropertyValue> {
+    let mut values =
+        vec![PropertyValue::new(EAST_ASIAN_WIDTH, "Neutral"); ucd::CODE_POINT_LIMIT as usize];
+
+    for (range, value) in ucd::read_property_file_with_defaults("EastAsianWidth.txt") {
+        let variant = property_enum::variant_for(&EAST_ASIAN_WIDTH_VALUES, &value);
+        for code in range {
+            values[code as usize] = PropertyValue::new(EAST_ASIAN_WIDTH, variant);
+        }
+    }
+
+    values
+}
+
+/// Return true iff `width` is Fullwidth, Wide or Halfwidth, i.e. the code point
+/// is "East Asian" for the purposes of UAX #14 rule LB30.
+pub fn is_east_asian(width: &PropertyValue) -> bool {
+    ["Fullwidth", "Wide", "Halfwidth"]
+        .iter()
+        .any(|variant| *width == PropertyValue::new(EAST_ASIAN_WIDTH, variant))
+}
+
+/// Compute the display width of every code point: 0 for nonspacing and
+/// enclosing marks, controls and default-ignorable code points, 2 for
+/// Fullwidth and Wide code points, and 1 for all others.
+fn compute_display_widths(
+    unicode_data: &[UnicodeDataRecord],
+    east_asian_widths: &[PropertyValue],
+) -> Vec<u8> {
+    let general_categories = ucd::general_categories(unicode_data);
+    let default_ignorable =
+        ucd::read_binary_property("DerivedCoreProperties.txt", "Default_Ignorable_Code_Point");
+
+    let fullwidth = PropertyValue::new(EAST_ASIAN_WIDTH, "Fullwidth");
+    let wide = PropertyValue::new(EAST_ASIAN_WIDTH, "Wide");
+
+    (0..ucd::CODE_POINT_LIMIT as usize)
+        .map(|code| {
+            if default_ignorable[code] || ["Mn", "Me", "Cc"].contains(&general_categories[code]) {
+                0
+            } else if east_asian_widths[code] == fullwidth || east_asian_widths[code] == wide {
+                2
+            } else {
+                1
+            }
+        })
+        .collect()
+}
+
+/// Generate the `EastAsianWidth` enum, the `east_asian_width` and
+/// `display_width` functions, and the tables backing them.
+pub fn generate_east_asian_width_functions(
+    unicode_data: &[UnicodeDataRecord],
+) -> proc_macro2::TokenStream {
+    let east_asian_width_enum = property_enum::generate_property_enum(
+        EAST_ASIAN_WIDTH,
+        "A value of the East_Asian_Width property, as defined by UAX #11.",
+        &EAST_ASIAN_WIDTH_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let east_asian_widths = compute_east_asian_widths();
+
+    let east_asian_width_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(EastAsianWidth),
+        "eastasianwidth",
+        "east_asian_width",
+        "Return the East_Asian_Width property of the provided code point.",
+        &east_asian_widths,
+    );
+
+    let display_width_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(u8),
+        "displaywidth",
+        "display_width",
+        r#"
+Return the number of columns the provided code point occupies when displayed in
+a terminal: 0 for nonspacing and enclosing marks (which combine with the
+preceding code point), controls and default-ignorable code points, 2 for
+Fullwidth and Wide code points, and 1 for all others.  Ambiguous code points
+are treated as narrow.
+"#
+        .trim(),
+        &compute_display_widths(unicode_data, &east_asian_widths),
+    );
+
+    quote! {
+        #east_asian_width_enum
+
+        #east_asian_width_fn
+
+        #display_width_fn
+    }
+}
@@ -0,0 +1,315 @@
+//! Generate the Grapheme_Cluster_Break, Extended_Pictographic and
+//! Indic_Conjunct_Break properties, and an iterator over the extended grapheme
+//! cluster boundaries in UTF-16 text as defined by UAX #29.
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::ucd;
+use quote::quote;
+
+const GRAPHEME_CLUSTER_BREAK: &str = "GraphemeClusterBreak";
+const INDIC_CONJUNCT_BREAK: &str = "IndicConjunctBreak";
+
+/// The `GraphemeClusterBreak` variant for each value in
+/// GraphemeBreakProperty.txt.  `Other` is the value of all unlisted code
+/// points.
+const GRAPHEME_CLUSTER_BREAK_VALUES: [(&str, &str); 14] = [
+    ("Other", "Other"),
+    ("CR", "Cr"),
+    ("LF", "Lf"),
+    ("Control", "Control"),
+    ("Extend", "Extend"),
+    ("ZWJ", "Zwj"),
+    ("Regional_Indicator", "RegionalIndicator"),
+    ("Prepend", "Prepend"),
+    ("SpacingMark", "SpacingMark"),
+    ("L", "L"),
+    ("V", "V"),
+    ("T", "T"),
+    ("LV", "Lv"),
+    ("LVT", "Lvt"),
+];
+
+/// The `IndicConjunctBreak` variant for each value of the InCB property in
+/// DerivedCoreProperties.txt.  `None` is the value of all unlisted code points.
+const INDIC_CONJUNCT_BREAK_VALUES: [(&str, &str); 4] = [
+    ("None", "None"),
+    ("Linker", "Linker"),
+    ("Consonant", "Consonant"),
+    ("Extend", "Extend"),
+];
+
+fn compute_grapheme_cluster_breaks() -> Vec<PropertyValue> {
+    let mut values =
+        vec![PropertyValue::new(GRAPHEME_CLUSTER_BREAK, "Other"); ucd::CODE_POINT_LIMIT as usize];
+
+    for (range, value) in ucd::read_property_file("GraphemeBreakProperty.txt") {
+        let variant = property_enum::variant_for(&GRAPHEME_CLUSTER_BREAK_VALUES, &value);
+        for code in range {
+            values[code as usize] = PropertyValue::new(GRAPHEME_CLUSTER_BREAK, variant);
+        }
+    }
+
+    values
+}
+
+/// Compute the Extended_Pictographic property of every code point from
+/// emoji-data.txt.
+pub fn compute_extended_pictographic() -> Vec<bool> {
+    ucd::read_binary_property("emoji-data.txt", "Extended_Pictographic")
+}
+
+fn compute_indic_conjunct_breaks() -> Vec<PropertyValue> {
+    let mut values =
+        vec![PropertyValue::new(INDIC_CONJUNCT_BREAK, "None"); ucd::CODE_POINT_LIMIT as usize];
+
+    let contents = ucd::read_data_file("DerivedCoreProperties.txt");
+    for fields in ucd::data_lines(&contents) {
+        if fields[1] != "InCB" {
+            continue;
+        }
+
+        let variant = property_enum::variant_for(&INDIC_CONJUNCT_BREAK_VALUES, fields[2]);
+        for code in ucd::parse_code_point_range(fields[0]) {
+            values[code as usize] = PropertyValue::new(INDIC_CONJUNCT_BREAK, variant);
+        }
+    }
+
+    values
+}
+
+/// Generate the `GraphemeClusterBreak` and `IndicConjunctBreak` enums, the
+/// `grapheme_cluster_break`, `is_extended_pictographic` and
+/// `indic_conjunct_break` functions, and the tables backing them.
+fn generate_grapheme_cluster_break_functions() -> proc_macro2::TokenStream {
+    let grapheme_cluster_break_enum = property_enum::generate_property_enum(
+        GRAPHEME_CLUSTER_BREAK,
+        "A value of the Grapheme_Cluster_Break property, as used in UAX #29.",
+        &GRAPHEME_CLUSTER_BREAK_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let indic_conjunct_break_enum = property_enum::generate_property_enum(
+        INDIC_CONJUNCT_BREAK,
+        "A value of the Indic_Conjunct_Break (InCB) property, as used in UAX #29.",
+        &INDIC_CONJUNCT_BREAK_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let grapheme_cluster_break_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(GraphemeClusterBreak),
+        "graphemebreak",
+        "grapheme_cluster_break",
+        "Return the Grapheme_Cluster_Break property of the provided code point.",
+        &compute_grapheme_cluster_breaks(),
+    );
+
+    let is_extended_pictographic_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(bool),
+        "extpict",
+        "is_extended_pictographic",
+        "Return true iff the provided code point has the Extended_Pictographic property.",
+        &compute_extended_pictographic(),
+    );
+
+    let indic_conjunct_break_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(IndicConjunctBreak),
+        "incb",
+        "indic_conjunct_break",
+        "Return the Indic_Conjunct_Break property of the provided code point.",
+        &compute_indic_conjunct_breaks(),
+    );
+
+    quote! {
+        #grapheme_cluster_break_enum
+
+        #indic_conjunct_break_enum
+
+        #grapheme_cluster_break_fn
+
+        #is_extended_pictographic_fn
+
+        #indic_conjunct_break_fn
+    }
+}
+
+/// Generate `GraphemeClusterBoundaries`, an iterator over the extended
+/// grapheme cluster boundaries in UTF-16 text.
+fn generate_grapheme_cluster_iterator() -> proc_macro2::TokenStream {
+    quote! {
+        /// The state of the text preceding a potential grapheme cluster
+        /// boundary, as far as is necessary to apply the rules of UAX #29.
+        #[derive(Clone, Copy)]
+        struct GraphemeClusterState {
+            /// The Grapheme_Cluster_Break of the preceding code point.
+            prev: GraphemeClusterBreak,
+            /// The number of consecutive Regional_Indicator code points
+            /// immediately preceding (GB12, GB13).
+            regional_indicators: usize,
+            /// True iff the preceding text matches
+            /// `\p{Extended_Pictographic} Extend*` (GB11).
+            pictographic: bool,
+            /// True iff the preceding text matches
+            /// `\p{Extended_Pictographic} Extend* ZWJ` (GB11).
+            pictographic_zwj: bool,
+            /// True iff the preceding text matches
+            /// `\p{InCB=Consonant} [\p{InCB=Extend}\p{InCB=Linker}]*` (GB9c).
+            conjunct: bool,
+            /// True iff `conjunct` and an `InCB=Linker` code point follows
+            /// the consonant (GB9c).
+            conjunct_linked: bool,
+        }
+
+        impl GraphemeClusterState {
+            fn new(code: u32) -> GraphemeClusterState {
+                let prev = grapheme_cluster_break(code);
+                GraphemeClusterState {
+                    prev,
+                    regional_indicators: (prev == GraphemeClusterBreak::RegionalIndicator) as usize,
+                    pictographic: is_extended_pictographic(code),
+                    pictographic_zwj: false,
+                    conjunct: indic_conjunct_break(code) == IndicConjunctBreak::Consonant,
+                    conjunct_linked: false,
+                }
+            }
+
+            /// Return true iff there's a grapheme cluster boundary between the
+            /// text this state describes and the code point `code`.
+            fn is_boundary_before(&self, code: u32) -> bool {
+                use GraphemeClusterBreak::*;
+
+                let next = grapheme_cluster_break(code);
+                match (self.prev, next) {
+                    // GB3
+                    (Cr, Lf) => false,
+                    // GB4, GB5
+                    (Cr, _) | (Lf, _) | (Control, _) | (_, Cr) | (_, Lf) | (_, Control) => true,
+                    // GB6, GB7, GB8
+                    (L, L) | (L, V) | (L, Lv) | (L, Lvt) => false,
+                    (Lv, V) | (Lv, T) | (V, V) | (V, T) => false,
+                    (Lvt, T) | (T, T) => false,
+                    // GB9, GB9a, GB9b
+                    (_, Extend) | (_, Zwj) | (_, SpacingMark) | (Prepend, _) => false,
+                    // GB12, GB13
+                    (RegionalIndicator, RegionalIndicator) => self.regional_indicators % 2 == 0,
+                    _ => {
+                        // GB9c
+                        if self.conjunct_linked
+                            && indic_conjunct_break(code) == IndicConjunctBreak::Consonant
+                        {
+                            return false;
+                        }
+
+                        // GB11
+                        if self.pictographic_zwj && is_extended_pictographic(code) {
+                            return false;
+                        }
+
+                        // GB999
+                        true
+                    }
+                }
+            }
+
+            /// Update this state to describe the text it describes followed by
+            /// the code point `code`.
+            fn advance(&mut self, code: u32) {
+                let next = grapheme_cluster_break(code);
+
+                self.regional_indicators = if next == GraphemeClusterBreak::RegionalIndicator {
+                    self.regional_indicators + 1
+                } else {
+                    0
+                };
+
+                self.pictographic_zwj = self.pictographic && next == GraphemeClusterBreak::Zwj;
+                self.pictographic = is_extended_pictographic(code)
+                    || (self.pictographic && next == GraphemeClusterBreak::Extend);
+
+                match indic_conjunct_break(code) {
+                    IndicConjunctBreak::Consonant => {
+                        self.conjunct = true;
+                        self.conjunct_linked = false;
+                    }
+                    IndicConjunctBreak::Linker if self.conjunct => {
+                        self.conjunct_linked = true;
+                    }
+                    IndicConjunctBreak::Extend if self.conjunct => {}
+                    _ => {
+                        self.conjunct = false;
+                        self.conjunct_linked = false;
+                    }
+                }
+
+                self.prev = next;
+            }
+        }
+
+        /// An iterator over the extended grapheme cluster boundaries in UTF-16
+        /// text, as defined by UAX #29.  Each boundary is exposed as the index
+        /// of the code unit just past the end of a grapheme cluster, so the
+        /// last boundary is the length of the text.  (The boundary at index 0
+        /// is not exposed.)  Unpaired surrogates are treated as Control.
+        ///
+        /// ```text
+        /// // "e\u{0301}a"
+        /// let text = [0x65, 0x301, 0x61];
+        /// let boundaries: Vec<usize> = GraphemeClusterBoundaries::new(&text).collect();
+        /// assert_eq!(boundaries, vec![2, 3]);
+        /// ```
+        pub struct GraphemeClusterBoundaries<'a> {
+            text: &'a [u16],
+            position: usize,
+        }
+
+        impl<'a> GraphemeClusterBoundaries<'a> {
+            pub fn new(text: &'a [u16]) -> GraphemeClusterBoundaries<'a> {
+                GraphemeClusterBoundaries { text, position: 0 }
+            }
+        }
+
+        impl<'a> Iterator for GraphemeClusterBoundaries<'a> {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                if self.position >= self.text.len() {
+                    return None;
+                }
+
+                let (code, length) = decode_utf16_code_point(self.text, self.position);
+                let mut state = GraphemeClusterState::new(code);
+                let mut end = self.position + length;
+
+                while end < self.text.len() {
+                    let (code, length) = decode_utf16_code_point(self.text, end);
+                    if state.is_boundary_before(code) {
+                        break;
+                    }
+
+                    state.advance(code);
+                    end += length;
+                }
+
+                self.position = end;
+                Some(end)
+            }
+        }
+    }
+}
+
+/// Generate the grapheme cluster break properties and segmentation iterator.
+pub fn generate_grapheme_cluster_code() -> proc_macro2::TokenStream {
+    let functions = generate_grapheme_cluster_break_functions();
+    let iterator = generate_grapheme_cluster_iterator();
+
+    quote! {
+        #functions
+
+        #iterator
+    }
+}
@@ -0,0 +1,163 @@
+//! Generate the Hangul_Syllable_Type property, and functions that compose and
+//! decompose Hangul syllables algorithmically, as described in section 3.12 of
+//! the Unicode Standard.
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::ucd;
+use quote::quote;
+
+const HANGUL_SYLLABLE_TYPE: &str = "HangulSyllableType";
+
+/// The `HangulSyllableType` variant for each value in HangulSyllableType.txt.
+/// `NotApplicable` is the value of all unlisted code points.
+const HANGUL_SYLLABLE_TYPE_VALUES: [(&str, &str); 6] = [
+    ("NA", "NotApplicable"),
+    ("L", "L"),
+    ("V", "V"),
+    ("T", "T"),
+    ("LV", "Lv"),
+    ("LVT", "Lvt"),
+];
+
+fn compute_hangul_syllable_types() -> Vec<PropertyValue> {
+    let mut values = vec![
+        PropertyValue::new(HANGUL_SYLLABLE_TYPE, "NotApplicable");
+        ucd::CODE_POINT_LIMIT as usize
+    ];
+
+    for (range, value) in ucd::read_property_file("HangulSyllableType.txt") {
+        let variant = property_enum::variant_for(&HANGUL_SYLLABLE_TYPE_VALUES, &value);
+        for code in range {
+            values[code as usize] = PropertyValue::new(HANGUL_SYLLABLE_TYPE, variant);
+        }
+    }
+
+    values
+}
+
+/// Generate the `HangulSyllableType` enum, the `hangul_syllable_type` function
+/// and the tables backing it, and the Hangul constants and composition and
+/// decomposition functions.
+pub fn generate_hangul_functions() -> proc_macro2::TokenStream {
+    let hangul_syllable_type_enum = property_enum::generate_property_enum(
+        HANGUL_SYLLABLE_TYPE,
+        "A value of the Hangul_Syllable_Type property.",
+        &HANGUL_SYLLABLE_TYPE_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let hangul_syllable_type_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(HangulSyllableType),
+        "hangulsyllabletype",
+        "hangul_syllable_type",
+        "Return the Hangul_Syllable_Type property of the provided code point.",
+        &compute_hangul_syllable_types(),
+    );
+
+    quote! {
+        #hangul_syllable_type_enum
+
+        #hangul_syllable_type_fn
+
+        /// The first Hangul syllable, U+AC00 HANGUL SYLLABLE GA (SBase).
+        pub const HANGUL_SYLLABLE_BASE: u32 = 0xAC00;
+
+        /// The first leading jamo, U+1100 HANGUL CHOSEONG KIYEOK (LBase).
+        pub const HANGUL_LEADING_BASE: u32 = 0x1100;
+
+        /// The first vowel jamo, U+1161 HANGUL JUNGSEONG A (VBase).
+        pub const HANGUL_VOWEL_BASE: u32 = 0x1161;
+
+        /// One less than the first trailing jamo, U+11A8 HANGUL JONGSEONG
+        /// KIYEOK (TBase).  A syllable's trailing jamo index of 0 denotes the
+        /// absence of a trailing jamo.
+        pub const HANGUL_TRAILING_BASE: u32 = 0x11A7;
+
+        /// The number of leading jamo that combine into Hangul syllables
+        /// (LCount).
+        pub const HANGUL_LEADING_COUNT: u32 = 19;
+
+        /// The number of vowel jamo that combine into Hangul syllables
+        /// (VCount).
+        pub const HANGUL_VOWEL_COUNT: u32 = 21;
+
+        /// The number of trailing jamo that combine into Hangul syllables,
+        /// plus one for the absence of a trailing jamo (TCount).
+        pub const HANGUL_TRAILING_COUNT: u32 = 28;
+
+        /// The number of Hangul syllables (SCount).
+        pub const HANGUL_SYLLABLE_COUNT: u32 =
+            HANGUL_LEADING_COUNT * HANGUL_VOWEL_COUNT * HANGUL_TRAILING_COUNT;
+
+        /// If the provided code point is a precomposed Hangul syllable, return
+        /// its full canonical decomposition: its leading jamo, its vowel jamo,
+        /// and its trailing jamo if it has one.
+        ///
+        /// ```text
+        /// // U+AC00 HANGUL SYLLABLE GA
+        /// assert_eq!(decompose_hangul_syllable(0xAC00), Some((0x1100, 0x1161, None)));
+        /// // U+AC01 HANGUL SYLLABLE GAG
+        /// assert_eq!(decompose_hangul_syllable(0xAC01), Some((0x1100, 0x1161, Some(0x11A8))));
+        /// assert_eq!(decompose_hangul_syllable('a' as u32), None);
+        /// ```
+        #[inline]
+        pub fn decompose_hangul_syllable(code: u32) -> Option<(u32, u32, Option<u32>)> {
+            let index = code.checked_sub(HANGUL_SYLLABLE_BASE)?;
+            if index >= HANGUL_SYLLABLE_COUNT {
+                return None;
+            }
+
+            let leading = HANGUL_LEADING_BASE + index / (HANGUL_VOWEL_COUNT * HANGUL_TRAILING_COUNT);
+            let vowel = HANGUL_VOWEL_BASE + (index / HANGUL_TRAILING_COUNT) % HANGUL_VOWEL_COUNT;
+            let trailing = match index % HANGUL_TRAILING_COUNT {
+                0 => None,
+                trailing_index => Some(HANGUL_TRAILING_BASE + trailing_index),
+            };
+
+            Some((leading, vowel, trailing))
+        }
+
+        /// Return the Hangul syllable that is the canonical composition of
+        /// `first` followed by `second`, if any: either a leading jamo followed
+        /// by a vowel jamo, which compose to an LV syllable, or an LV syllable
+        /// followed by a trailing jamo, which compose to an LVT syllable.
+        ///
+        /// ```text
+        /// assert_eq!(compose_hangul(0x1100, 0x1161), Some(0xAC00));
+        /// assert_eq!(compose_hangul(0xAC00, 0x11A8), Some(0xAC01));
+        /// assert_eq!(compose_hangul(0xAC01, 0x11A8), None);
+        /// ```
+        #[inline]
+        pub fn compose_hangul(first: u32, second: u32) -> Option<u32> {
+            if let (Some(leading_index), Some(vowel_index)) = (
+                first.checked_sub(HANGUL_LEADING_BASE),
+                second.checked_sub(HANGUL_VOWEL_BASE),
+            ) {
+                if leading_index < HANGUL_LEADING_COUNT && vowel_index < HANGUL_VOWEL_COUNT {
+                    let index = (leading_index * HANGUL_VOWEL_COUNT + vowel_index)
+                        * HANGUL_TRAILING_COUNT;
+                    return Some(HANGUL_SYLLABLE_BASE + index);
+                }
+            }
+
+            if let (Some(syllable_index), Some(trailing_index)) = (
+                first.checked_sub(HANGUL_SYLLABLE_BASE),
+                second.checked_sub(HANGUL_TRAILING_BASE),
+            ) {
+                if syllable_index < HANGUL_SYLLABLE_COUNT
+                    && syllable_index % HANGUL_TRAILING_COUNT == 0
+                    && 0 < trailing_index
+                    && trailing_index < HANGUL_TRAILING_COUNT
+                {
+                    return Some(first + trailing_index);
+                }
+            }
+
+            None
+        }
+    }
+}
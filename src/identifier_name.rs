@@ -0,0 +1,53 @@
+//! Generate functions that validate and scan ECMAScript IdentifierNames in
+//! UTF-16 text.
+
+use quote::quote;
+
+/// Generate `identifier_name_prefix_length` and `is_identifier_name`.  These
+/// depend upon `is_identifier_start` and `is_identifier_part`, and upon
+/// `decode_utf16_code_point`.
+pub fn generate_identifier_name_functions() -> proc_macro2::TokenStream {
+    quote! {
+        /// Return the length, in code units, of the longest prefix of `text`
+        /// that is matched by the ECMAScript IdentifierName production
+        /// (excluding Unicode escape sequences), or 0 if `text` doesn't begin
+        /// with an IdentifierStart code point.  Surrogate pairs are decoded;
+        /// unpaired surrogates end the identifier.
+        ///
+        /// ```text
+        /// // "foo.bar"
+        /// assert_eq!(identifier_name_prefix_length(&[0x66, 0x6F, 0x6F, 0x2E, 0x62, 0x61, 0x72]), 3);
+        /// assert_eq!(identifier_name_prefix_length(&[0x31, 0x61]), 0);
+        /// ```
+        pub fn identifier_name_prefix_length(text: &[u16]) -> usize {
+            if text.is_empty() {
+                return 0;
+            }
+
+            let (code, mut index) = decode_utf16_code_point(text, 0);
+            if !is_identifier_start(code) {
+                return 0;
+            }
+
+            while index < text.len() {
+                let (code, length) = decode_utf16_code_point(text, index);
+                if !is_identifier_part(code) {
+                    break;
+                }
+                index += length;
+            }
+            index
+        }
+
+        /// Return true iff all of `text` is matched by the ECMAScript
+        /// IdentifierName production (excluding Unicode escape sequences).
+        ///
+        /// ```text
+        /// assert!(is_identifier_name(&[0x24, 0x31])); // "$1"
+        /// assert!(!is_identifier_name(&[]));
+        /// ```
+        pub fn is_identifier_name(text: &[u16]) -> bool {
+            !text.is_empty() && identifier_name_prefix_length(text) == text.len()
+        }
+    }
+}
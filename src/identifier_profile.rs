@@ -0,0 +1,223 @@
+//! Parse the identifier profile option accepted by `generate_unicode_tables!`,
+//! and generate identifier tables and functions based upon the UAX #31
+//! XID_Start and XID_Continue properties.
+//!
+//! The ECMAScript profile (the default) generates the `isidstart` and
+//! `isident` ASCII tables and the `is_identifier_{start,part}_non_bmp`
+//! functions, which follow the ECMAScript IdentifierStart and IdentifierPart
+//! productions.  The XID profile instead generates their XID equivalents,
+//! which are closed under NFKC and suit languages such as Rust and Python.
+//! Both may be generated together:
+//!
+//! ```text
+//! generate_unicode_tables!();                              // ECMAScript
+//! generate_unicode_tables!(identifier_profile = xid);
+//! generate_unicode_tables!(identifier_profile = both);
+//! ```
+
+use crate::ascii_tables;
+use crate::code_point_lookup;
+use crate::supplemental_identifier_function;
+use crate::ucd;
+use proc_macro2::TokenTree;
+use quote::quote;
+use unicode_info::types::CodePointSet;
+
+/// Which identifier tables and functions to generate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdentifierProfile {
+    /// The ECMAScript IdentifierStart and IdentifierPart productions.
+    EcmaScript,
+    /// The UAX #31 XID_Start and XID_Continue properties.
+    Xid,
+    /// Both of the above.
+    Both,
+}
+
+impl IdentifierProfile {
+    pub fn includes_ecmascript(self) -> bool {
+        self != IdentifierProfile::Xid
+    }
+
+    pub fn includes_xid(self) -> bool {
+        self != IdentifierProfile::EcmaScript
+    }
+}
+
+/// Parse the input to `generate_unicode_tables!`: either nothing, or
+/// `identifier_profile = ecmascript`, `xid` or `both`, optionally followed by a
+/// comma.
+pub fn parse_identifier_profile(input: proc_macro2::TokenStream) -> IdentifierProfile {
+    const USAGE: &str = "expected `identifier_profile = ecmascript | xid | both`";
+
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let tokens = match tokens.split_last() {
+        Some((TokenTree::Punct(comma), rest)) if comma.as_char() == ',' => rest,
+        _ => &tokens[..],
+    };
+
+    match tokens {
+        [] => IdentifierProfile::EcmaScript,
+        [TokenTree::Ident(option), TokenTree::Punct(equals), TokenTree::Ident(profile)]
+            if option == "identifier_profile" && equals.as_char() == '=' =>
+        {
+            match profile.to_string().as_str() {
+                "ecmascript" => IdentifierProfile::EcmaScript,
+                "xid" => IdentifierProfile::Xid,
+                "both" => IdentifierProfile::Both,
+                _ => panic!("unknown identifier profile `{}`: {}", profile, USAGE),
+            }
+        }
+        _ => panic!("{}", USAGE),
+    }
+}
+
+/// Generate the `isxidstart` and `isxidcontinue` ASCII tables, the
+/// `is_xid_{start,continue}_non_bmp` functions, and the full-range
+/// `is_xid_start` and `is_xid_continue` functions.
+pub fn generate_xid_functions() -> proc_macro2::TokenStream {
+    let xid_start = ucd::read_binary_property("DerivedCoreProperties.txt", "XID_Start");
+    let xid_continue = ucd::read_binary_property("DerivedCoreProperties.txt", "XID_Continue");
+
+    let isxidstart_table = ascii_tables::generate_ascii_table(
+        "isxidstart",
+        r#"
+A lookup table storing at index `i` whether the ASCII code point with value `i`
+has the XID_Start property, allowing it to appear at the start of a UAX #31
+identifier.
+        "#
+        .trim(),
+        &|code| xid_start[code as usize],
+    );
+
+    let isxidcontinue_table = ascii_tables::generate_ascii_table(
+        "isxidcontinue",
+        r#"
+A lookup table storing at index `i` whether the ASCII code point with value `i`
+has the XID_Continue property, allowing it to appear within a UAX #31
+identifier after its starting character.
+        "#
+        .trim(),
+        &|code| xid_continue[code as usize],
+    );
+
+    // Only BMP code points are looked up in `xidflags`: the non-BMP functions
+    // handle the rest.
+    let bmp_flags: Vec<u8> = (0..ucd::CODE_POINT_LIMIT)
+        .map(|code| {
+            let code = code as usize;
+            if code > 0xFFFF {
+                0
+            } else {
+                xid_start[code] as u8 | (xid_continue[code] as u8) << 1
+            }
+        })
+        .collect();
+    let xid_flags_fn = code_point_lookup::generate_private_code_point_lookup(
+        quote!(u8),
+        "xidflags",
+        "xid_flags",
+        "Return 1 if the provided BMP code point is XID_Start, plus 2 if it's XID_Continue.",
+        &bmp_flags,
+    );
+
+    let non_bmp_set = |values: &[bool]| -> CodePointSet {
+        (0x10000..ucd::CODE_POINT_LIMIT)
+            .filter(|code| values[*code as usize])
+            .collect()
+    };
+
+    let is_xid_start_non_bmp_fn =
+        supplemental_identifier_function::generate_supplemental_identifer_function(
+            "is_xid_start_non_bmp",
+            r#"
+Return true iff the provided _non-BMP_ code point has the XID_Start property.
+
+It is an error to call this function with a BMP code point, i.e. one whose value
+is 0xFFFF or lower.
+            "#
+            .trim(),
+            &non_bmp_set(&xid_start),
+        );
+
+    let is_xid_continue_non_bmp_fn =
+        supplemental_identifier_function::generate_supplemental_identifer_function(
+            "is_xid_continue_non_bmp",
+            r#"
+Return true iff the provided _non-BMP_ code point has the XID_Continue property.
+
+It is an error to call this function with a BMP code point, i.e. one whose value
+is 0xFFFF or lower.
+            "#
+            .trim(),
+            &non_bmp_set(&xid_continue),
+        );
+
+    quote! {
+        #isxidstart_table
+
+        #isxidcontinue_table
+
+        #xid_flags_fn
+
+        #is_xid_start_non_bmp_fn
+
+        #is_xid_continue_non_bmp_fn
+
+        /// Return true iff the provided code point has the XID_Start property,
+        /// allowing it to appear at the start of a UAX #31 identifier.
+        ///
+        /// ```text
+        /// assert!(is_xid_start('a' as u32));
+        /// assert!(!is_xid_start('_' as u32));
+        /// ```
+        #[inline]
+        pub fn is_xid_start(code: u32) -> bool {
+            if code < 0x80 {
+                isxidstart[code as usize]
+            } else if code <= 0xFFFF {
+                xid_flags(code) & 1 != 0
+            } else {
+                is_xid_start_non_bmp(code)
+            }
+        }
+
+        /// Return true iff the provided code point has the XID_Continue
+        /// property, allowing it to appear within a UAX #31 identifier after
+        /// its starting character.
+        ///
+        /// ```text
+        /// assert!(is_xid_continue('_' as u32));
+        /// assert!(is_xid_continue('1' as u32));
+        /// assert!(!is_xid_continue('$' as u32));
+        /// ```
+        #[inline]
+        pub fn is_xid_continue(code: u32) -> bool {
+            if code < 0x80 {
+                isxidcontinue[code as usize]
+            } else if code <= 0xFFFF {
+                xid_flags(code) & 2 != 0
+            } else {
+                is_xid_continue_non_bmp(code)
+            }
+        }
+    }
+}
+
+#[test]
+fn check_parse_identifier_profile() {
+    let parse = |input: &str| parse_identifier_profile(input.parse().unwrap());
+
+    assert_eq!(parse(""), IdentifierProfile::EcmaScript);
+    assert_eq!(
+        parse("identifier_profile = ecmascript"),
+        IdentifierProfile::EcmaScript
+    );
+    assert_eq!(parse("identifier_profile = xid"), IdentifierProfile::Xid);
+    assert_eq!(parse("identifier_profile = both,"), IdentifierProfile::Both);
+
+    assert!(IdentifierProfile::Both.includes_ecmascript());
+    assert!(IdentifierProfile::Both.includes_xid());
+    assert!(!IdentifierProfile::Xid.includes_ecmascript());
+    assert!(!IdentifierProfile::EcmaScript.includes_xid());
+}
@@ -0,0 +1,144 @@
+//! Generate the Identifier_Status and Identifier_Type properties defined by
+//! UTS #39, Unicode Security Mechanisms.
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::supplemental_identifier_function;
+use crate::ucd;
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::quote;
+use unicode_info::types::CodePointSet;
+
+/// Each Identifier_Type value in IdentifierType.txt, and the name of the
+/// constant for its bit in the bit set returned by `identifier_types`.
+const IDENTIFIER_TYPES: [(&str, &str); 12] = [
+    ("Not_Character", "IDENTIFIER_TYPE_NOT_CHARACTER"),
+    ("Deprecated", "IDENTIFIER_TYPE_DEPRECATED"),
+    ("Default_Ignorable", "IDENTIFIER_TYPE_DEFAULT_IGNORABLE"),
+    ("Not_NFKC", "IDENTIFIER_TYPE_NOT_NFKC"),
+    ("Not_XID", "IDENTIFIER_TYPE_NOT_XID"),
+    ("Exclusion", "IDENTIFIER_TYPE_EXCLUSION"),
+    ("Obsolete", "IDENTIFIER_TYPE_OBSOLETE"),
+    ("Technical", "IDENTIFIER_TYPE_TECHNICAL"),
+    ("Uncommon_Use", "IDENTIFIER_TYPE_UNCOMMON_USE"),
+    ("Limited_Use", "IDENTIFIER_TYPE_LIMITED_USE"),
+    ("Inclusion", "IDENTIFIER_TYPE_INCLUSION"),
+    ("Recommended", "IDENTIFIER_TYPE_RECOMMENDED"),
+];
+
+/// Compute the set of code points whose Identifier_Status is Allowed.  All
+/// other code points are Restricted.
+fn compute_allowed_set() -> CodePointSet {
+    let mut allowed = CodePointSet::new();
+    for (range, value) in ucd::read_property_file("IdentifierStatus.txt") {
+        match value.as_str() {
+            "Allowed" => allowed.extend(range),
+            "Restricted" => {}
+            _ => panic!("unexpected Identifier_Status value {:?}", value),
+        }
+    }
+
+    allowed
+}
+
+/// Compute the Identifier_Type of every code point, as a bit set of
+/// `1 << i` for each `IDENTIFIER_TYPES[i]` it has.  A code point may have
+/// several types, separated by spaces in IdentifierType.txt.  Code points not
+/// listed have the file's `@missing` type, Not_Character.
+fn compute_identifier_types() -> Vec<u16> {
+    let mut values = vec![0u16; ucd::CODE_POINT_LIMIT as usize];
+    for (range, value) in ucd::read_property_file_with_defaults("IdentifierType.txt") {
+        let types = value.split_whitespace().fold(0, |types, identifier_type| {
+            let bit = IDENTIFIER_TYPES
+                .iter()
+                .position(|(name, _)| *name == identifier_type)
+                .unwrap_or_else(|| panic!("unexpected Identifier_Type {:?}", identifier_type));
+            types | 1 << bit
+        });
+
+        for code in range {
+            values[code as usize] = types;
+        }
+    }
+
+    values
+}
+
+/// Generate the `IdentifierStatus` enum, `identifier_status`, and
+/// `identifier_types` and the `IDENTIFIER_TYPE_*` bits it returns.
+pub fn generate_identifier_security_functions() -> proc_macro2::TokenStream {
+    let identifier_status_enum = property_enum::generate_property_enum(
+        "IdentifierStatus",
+        "A value of the Identifier_Status property, as defined by UTS #39.",
+        &["Restricted", "Allowed"],
+    );
+
+    let is_identifier_allowed_fn =
+        supplemental_identifier_function::generate_supplemental_identifer_function(
+            "is_identifier_allowed",
+            r#"
+Return true iff the provided code point has Identifier_Status=Allowed, i.e. it
+is recommended for use in identifiers by UTS #39.
+            "#
+            .trim(),
+            &compute_allowed_set(),
+        );
+
+    let identifier_types_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(u16),
+        "identifiertypes",
+        "identifier_types",
+        r#"
+Return the Identifier_Type property of the provided code point, as a bit set of
+the `IDENTIFIER_TYPE_*` constants, one for each of its types.  Unassigned,
+private-use and surrogate code points have type Not_Character.
+"#
+        .trim(),
+        &compute_identifier_types(),
+    );
+
+    let type_constants =
+        IDENTIFIER_TYPES
+            .iter()
+            .enumerate()
+            .map(|(bit, (identifier_type, constant_name))| {
+                let mask = 1u16 << bit;
+                let doc = format!(
+                    "The bit set by `identifier_types` for a code point of Identifier_Type {}.",
+                    identifier_type
+                );
+                let constant_name = Ident::new(constant_name, Span::call_site());
+
+                quote! {
+                    #[doc = #doc]
+                    pub const #constant_name: u16 = #mask;
+                }
+            });
+
+    quote! {
+        #identifier_status_enum
+
+        #is_identifier_allowed_fn
+
+        /// Return the Identifier_Status property of the provided code point.
+        ///
+        /// ```text
+        /// assert_eq!(identifier_status('a' as u32), IdentifierStatus::Allowed);
+        /// // U+2460 CIRCLED DIGIT ONE
+        /// assert_eq!(identifier_status(0x2460), IdentifierStatus::Restricted);
+        /// ```
+        #[inline]
+        pub fn identifier_status(code: u32) -> IdentifierStatus {
+            if is_identifier_allowed(code) {
+                IdentifierStatus::Allowed
+            } else {
+                IdentifierStatus::Restricted
+            }
+        }
+
+        #identifier_types_fn
+
+        #( #type_constants )*
+    }
+}
@@ -0,0 +1,589 @@
+//! Generate the IDNA mapping table defined by UTS #46, Unicode IDNA
+//! Compatibility Processing, and functions applying it and the rest of the
+//! UTS #46 processing steps to domain names.
+//!
+//! Each range of code points with the same status and mapping is stored once,
+//! with the offset and length of its mapping, in UTF-16, in a shared table of
+//! replacement code units.
+
+use crate::code_point_lookup;
+use crate::generate_table;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::range_table;
+use crate::ucd;
+use crate::ucd::UnicodeDataRecord;
+use quote::quote;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+const IDNA_STATUS: &str = "IdnaStatus";
+
+/// The `IdnaStatus` variant for each status in IdnaMappingTable.txt.
+const IDNA_STATUS_VALUES: [(&str, &str); 7] = [
+    ("valid", "Valid"),
+    ("ignored", "Ignored"),
+    ("mapped", "Mapped"),
+    ("deviation", "Deviation"),
+    ("disallowed", "Disallowed"),
+    ("disallowed_STD3_valid", "DisallowedStd3Valid"),
+    ("disallowed_STD3_mapped", "DisallowedStd3Mapped"),
+];
+
+/// The status of a range of code points, and the offset and length in
+/// `idna_mapping_data` of their mapping, converted to tokens as a tuple.
+struct IdnaEntry {
+    status: PropertyValue,
+    offset: u16,
+    length: u16,
+}
+
+impl quote::ToTokens for IdnaEntry {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let IdnaEntry {
+            status,
+            offset,
+            length,
+        } = self;
+        tokens.extend(quote! { (#status, #offset, #length) });
+    }
+}
+
+/// Encode `code_points` in UTF-16.
+fn encode_utf16(code_points: &[u32]) -> Vec<u16> {
+    let mut units = vec![];
+    for code in code_points {
+        let c = char::from_u32(*code).unwrap_or_else(|| panic!("bad mapping {:04X}", code));
+        let mut buffer = [0; 2];
+        units.extend_from_slice(c.encode_utf16(&mut buffer));
+    }
+    units
+}
+
+/// Read IdnaMappingTable.txt, returning the ranges of code points in it merged
+/// where adjacent ranges have the same status and mapping, in code point
+/// order, and the UTF-16 mapping data that the ranges index into.
+fn read_idna_mapping() -> (Vec<(RangeInclusive<u32>, IdnaEntry)>, Vec<u16>) {
+    let contents = ucd::read_data_file("IdnaMappingTable.txt");
+
+    let mut data = vec![];
+    let mut offsets = BTreeMap::new();
+    let mut ranges: Vec<(RangeInclusive<u32>, &str, Vec<u16>)> = vec![];
+    for fields in ucd::data_lines(&contents) {
+        let range = ucd::parse_code_point_range(fields[0]);
+        let status = property_enum::variant_for(&IDNA_STATUS_VALUES, fields[1]);
+        let mapping = encode_utf16(&ucd::parse_code_point_sequence(
+            fields.get(2).copied().unwrap_or(""),
+        ));
+
+        match ranges.last_mut() {
+            Some((last_range, last_status, last_mapping))
+                if *last_range.end() + 1 == *range.start()
+                    && *last_status == status
+                    && *last_mapping == mapping =>
+            {
+                *last_range = *last_range.start()..=*range.end();
+            }
+            _ => ranges.push((range, status, mapping)),
+        }
+    }
+
+    let ranges = ranges
+        .into_iter()
+        .map(|(range, status, mapping)| {
+            let offset = *offsets.entry(mapping.clone()).or_insert_with(|| {
+                let offset = data.len();
+                data.extend_from_slice(&mapping);
+                offset
+            });
+            assert!(offset <= u16::MAX as usize, "too much IDNA mapping data");
+
+            let entry = IdnaEntry {
+                status: PropertyValue::new(IDNA_STATUS, status),
+                offset: offset as u16,
+                length: mapping.len() as u16,
+            };
+            (range, entry)
+        })
+        .collect();
+
+    (ranges, data)
+}
+
+/// Compute whether the General_Category of every code point is a Mark (Mn, Mc
+/// or Me).
+fn compute_combining_marks(unicode_data: &[UnicodeDataRecord]) -> Vec<bool> {
+    ucd::general_categories(unicode_data)
+        .iter()
+        .map(|category| category.starts_with('M'))
+        .collect()
+}
+
+/// Generate the `IdnaStatus` enum, the IDNA mapping tables, `idna_status`,
+/// `idna_mapping`, `length_idna_mapping`, `append_idna_mapping` and `idna_map`,
+/// and the `IdnaOptions` struct and `idna_process`.
+pub fn generate_idna_mapping_functions(
+    unicode_data: &[UnicodeDataRecord],
+) -> proc_macro2::TokenStream {
+    let idna_status_enum = property_enum::generate_property_enum(
+        IDNA_STATUS,
+        "The status of a code point in the IDNA mapping table of UTS #46.",
+        &IDNA_STATUS_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let (ranges, data) = read_idna_mapping();
+
+    let idna_mapping_data_table = generate_table::generate_table(
+        quote!(u16),
+        "idna_mapping_data",
+        "The UTF-16 code units of the mappings in `idna_mapping_ranges`, one after another.",
+        &data,
+    );
+
+    let idna_mapping_entry_fn = range_table::generate_private_range_lookup(
+        quote!((IdnaStatus, u16, u16)),
+        "idna_mapping_ranges",
+        "idna_mapping_entry",
+        r#"
+Return the IDNA status of the provided code point, and the offset and length in
+`idna_mapping_data` of its mapping, or `None` if it isn't listed.
+"#
+        .trim(),
+        &ranges,
+    );
+
+    let is_combining_mark_fn = code_point_lookup::generate_private_code_point_lookup(
+        quote!(bool),
+        "iscombiningmark",
+        "is_combining_mark",
+        "Return true iff the General_Category of the provided code point is a Mark (Mn, Mc or Me).",
+        &compute_combining_marks(unicode_data),
+    );
+
+    quote! {
+        #idna_status_enum
+
+        #idna_mapping_data_table
+
+        #idna_mapping_entry_fn
+
+        #is_combining_mark_fn
+
+        /// Return the status of the provided code point in the IDNA mapping
+        /// table.  Code points that aren't listed are `Disallowed`.
+        ///
+        /// ```text
+        /// assert_eq!(idna_status('a' as u32), IdnaStatus::Valid);
+        /// assert_eq!(idna_status('A' as u32), IdnaStatus::Mapped);
+        /// assert_eq!(idna_status(0xDF), IdnaStatus::Deviation); // ß
+        /// ```
+        pub fn idna_status(code: u32) -> IdnaStatus {
+            idna_mapping_entry(code).map_or(IdnaStatus::Disallowed, |(status, _, _)| status)
+        }
+
+        /// Return the UTF-16 code units of the mapping of the provided code
+        /// point in the IDNA mapping table.  This is empty unless its status is
+        /// `Mapped`, `Deviation` or `DisallowedStd3Mapped`, and may be empty
+        /// for a `Deviation`.
+        ///
+        /// ```text
+        /// assert_eq!(idna_mapping('A' as u32), ['a' as u16]);
+        /// assert_eq!(idna_mapping(0xDF), ['s' as u16, 's' as u16]); // ß
+        /// assert!(idna_mapping('a' as u32).is_empty());
+        /// ```
+        pub fn idna_mapping(code: u32) -> &'static [u16] {
+            match idna_mapping_entry(code) {
+                Some((_, offset, length)) => {
+                    let offset = offset as usize;
+                    &idna_mapping_data[offset..offset + length as usize]
+                }
+                None => &[],
+            }
+        }
+
+        /// Return the number of UTF-16 code units in the mapping of the
+        /// provided code point in the IDNA mapping table, i.e. the number that
+        /// `append_idna_mapping` writes.
+        ///
+        /// ```text
+        /// assert_eq!(length_idna_mapping(0xDF), 2); // ß -> ss
+        /// ```
+        #[no_mangle]
+        pub extern "C" fn length_idna_mapping(code: u32) -> usize {
+            idna_mapping(code).len()
+        }
+
+        /// Write the UTF-16 code units of the mapping of the provided code
+        /// point in the IDNA mapping table to `elements[*index]`, incrementing
+        /// `*index` by the number of code units written.  Nothing is written
+        /// for a code point whose mapping is empty.
+        ///
+        /// It is presumed that properly-owned memory exists at these addresses
+        /// -- typically by calling `length_idna_mapping` and using the value it
+        /// returns to provide such memory.
+        #[no_mangle]
+        pub unsafe extern "C" fn append_idna_mapping(code: u32, elements: *mut u16, index: *mut usize) {
+            let mapping = idna_mapping(code);
+            let ptr = elements.add(index.read());
+            ptr.copy_from_nonoverlapping(mapping.as_ptr(), mapping.len());
+            index.write(mapping.len() + index.read());
+        }
+
+        /// Apply the mapping step of UTS #46 section 4 to the domain name
+        /// `text`, appending the result to `output`.  Return false if a
+        /// disallowed code point was encountered; it's appended unchanged, so
+        /// that processing can continue, as the standard requires.
+        ///
+        /// `transitional` selects transitional processing, in which deviations
+        /// such as "ß" are mapped.  `use_std3_ascii_rules` disallows ASCII
+        /// code points outside the LDH (letter, digit, hyphen) set, and the
+        /// code points mapping to them.
+        ///
+        /// This is only the first processing step; `idna_process` performs
+        /// them all.
+        ///
+        /// ```text
+        /// let mut output = vec![];
+        /// // "Aß", U+3002 IDEOGRAPHIC FULL STOP
+        /// assert!(idna_map(&[0x41, 0xDF, 0x3002], false, true, &mut output));
+        /// assert_eq!(output, [0x61, 0xDF, 0x2E]);
+        /// ```
+        pub fn idna_map(
+            text: &[u16],
+            transitional: bool,
+            use_std3_ascii_rules: bool,
+            output: &mut Vec<u16>,
+        ) -> bool {
+            let mut valid = true;
+            for code in utf16_code_points(text) {
+                let mapped = match idna_status(code) {
+                    IdnaStatus::Valid => false,
+                    IdnaStatus::Ignored => continue,
+                    IdnaStatus::Mapped => true,
+                    IdnaStatus::Deviation => transitional,
+                    IdnaStatus::Disallowed => {
+                        valid = false;
+                        false
+                    }
+                    IdnaStatus::DisallowedStd3Valid => {
+                        valid &= !use_std3_ascii_rules;
+                        false
+                    }
+                    IdnaStatus::DisallowedStd3Mapped => {
+                        valid &= !use_std3_ascii_rules;
+                        !use_std3_ascii_rules
+                    }
+                };
+
+                if mapped {
+                    output.extend_from_slice(idna_mapping(code));
+                } else {
+                    push_utf16_code_point(output, code);
+                }
+            }
+
+            valid
+        }
+
+        /// The options of UTS #46 processing, named as in section 4.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct IdnaOptions {
+            /// Transitional_Processing: map deviations such as "ß" rather than
+            /// keeping them.
+            pub transitional: bool,
+            /// UseSTD3ASCIIRules: disallow ASCII code points outside the LDH
+            /// (letter, digit, hyphen) set, and the code points mapping to
+            /// them.
+            pub use_std3_ascii_rules: bool,
+            /// CheckHyphens: disallow labels beginning or ending with '-', or
+            /// with '-' in both their third and fourth positions.
+            pub check_hyphens: bool,
+            /// CheckBidi: require every label of a domain name containing
+            /// right-to-left code points to satisfy the Bidi Rule of RFC 5893.
+            pub check_bidi: bool,
+            /// CheckJoiners: permit ZERO WIDTH JOINER and ZERO WIDTH NON-JOINER
+            /// only in the contexts `is_joiner_permitted` accepts.
+            pub check_joiners: bool,
+        }
+
+        /// Decode `input`, the Punycode (RFC 3492) following "xn--" in a
+        /// label, returning the code points it encodes, or `None` if it isn't
+        /// valid Punycode.
+        fn decode_punycode(input: &[u16]) -> Option<Vec<u32>> {
+            const BASE: u32 = 36;
+            const T_MIN: u32 = 1;
+            const T_MAX: u32 = 26;
+            const SKEW: u32 = 38;
+            const DAMP: u32 = 700;
+            const INITIAL_BIAS: u32 = 72;
+            const INITIAL_N: u32 = 0x80;
+
+            fn adapt(delta: u32, length: u32, first_time: bool) -> u32 {
+                let mut delta = if first_time { delta / DAMP } else { delta / 2 };
+                delta += delta / length;
+                let mut k = 0;
+                while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+                    delta /= BASE - T_MIN;
+                    k += BASE;
+                }
+                k + (BASE - T_MIN + 1) * delta / (delta + SKEW)
+            }
+
+            // Basic code points precede the last delimiter, if there is one.
+            let (basic, extended) = match input.iter().rposition(|unit| *unit == 0x2D) {
+                Some(delimiter) => (&input[..delimiter], &input[delimiter + 1..]),
+                None => (&[][..], input),
+            };
+            if basic.iter().any(|unit| *unit >= 0x80) {
+                return None;
+            }
+            let mut decoded: Vec<u32> = basic.iter().map(|unit| u32::from(*unit)).collect();
+
+            let mut n = INITIAL_N;
+            let mut i: u32 = 0;
+            let mut bias = INITIAL_BIAS;
+            let mut position = 0;
+            while position < extended.len() {
+                let old_i = i;
+                let mut weight: u32 = 1;
+                let mut k = BASE;
+                loop {
+                    let digit = u32::from(match *extended.get(position)? {
+                        unit @ 0x61..=0x7A => unit - 0x61,
+                        unit @ 0x41..=0x5A => unit - 0x41,
+                        unit @ 0x30..=0x39 => unit - 0x30 + 26,
+                        _ => return None,
+                    });
+                    position += 1;
+
+                    i = i.checked_add(digit.checked_mul(weight)?)?;
+                    let t = if k <= bias {
+                        T_MIN
+                    } else if k >= bias + T_MAX {
+                        T_MAX
+                    } else {
+                        k - bias
+                    };
+                    if digit < t {
+                        break;
+                    }
+                    weight = weight.checked_mul(BASE - t)?;
+                    k += BASE;
+                }
+
+                let length = decoded.len() as u32 + 1;
+                bias = adapt(i - old_i, length, old_i == 0);
+                n = n.checked_add(i / length)?;
+                i %= length;
+                // Surrogates and values beyond 0x10FFFF aren't code points.
+                char::from_u32(n)?;
+                decoded.insert(i as usize, n);
+                i += 1;
+            }
+
+            Some(decoded)
+        }
+
+        /// Return true iff the code points of `label` satisfy the six
+        /// conditions of the Bidi Rule in RFC 5893 section 2.
+        fn satisfies_bidi_rule(label: &[u32]) -> bool {
+            let classes: Vec<BidiClass> = label.iter().map(|code| bidi_class(*code)).collect();
+
+            // The last class other than NSM.
+            let last = classes.iter().rev().find(|class| **class != BidiClass::Nsm);
+
+            match classes.first() {
+                Some(BidiClass::R | BidiClass::Al) => {
+                    classes.iter().all(|class| {
+                        matches!(
+                            class,
+                            BidiClass::R
+                                | BidiClass::Al
+                                | BidiClass::An
+                                | BidiClass::En
+                                | BidiClass::Es
+                                | BidiClass::Cs
+                                | BidiClass::Et
+                                | BidiClass::On
+                                | BidiClass::Bn
+                                | BidiClass::Nsm
+                        )
+                    }) && matches!(
+                        last,
+                        Some(BidiClass::R | BidiClass::Al | BidiClass::En | BidiClass::An)
+                    ) && !(classes.contains(&BidiClass::En) && classes.contains(&BidiClass::An))
+                }
+                Some(BidiClass::L) => {
+                    classes.iter().all(|class| {
+                        matches!(
+                            class,
+                            BidiClass::L
+                                | BidiClass::En
+                                | BidiClass::Es
+                                | BidiClass::Cs
+                                | BidiClass::Et
+                                | BidiClass::On
+                                | BidiClass::Bn
+                                | BidiClass::Nsm
+                        )
+                    }) && matches!(last, Some(BidiClass::L | BidiClass::En))
+                }
+                _ => false,
+            }
+        }
+
+        /// Return true iff `label`, a label of a mapped and normalized domain
+        /// name, meets the validity criteria of UTS #46 section 4.1.
+        /// `transitional` selects the processing the label is validated under,
+        /// which is never transitional for a label decoded from Punycode.
+        fn is_valid_idna_label(
+            label: &[u16],
+            transitional: bool,
+            options: IdnaOptions,
+            bidi_domain_name: bool,
+        ) -> bool {
+            const HYPHEN_MINUS: u32 = 0x2D;
+            const FULL_STOP: u32 = 0x2E;
+
+            let code_points: Vec<u32> = utf16_code_points(label).collect();
+            let (first, last) = match (code_points.first(), code_points.last()) {
+                (Some(first), Some(last)) => (*first, *last),
+                _ => return true,
+            };
+
+            if to_nfc(label) != label {
+                return false;
+            }
+
+            if options.check_hyphens {
+                if code_points.get(2..4) == Some(&[HYPHEN_MINUS, HYPHEN_MINUS][..]) {
+                    return false;
+                }
+                if first == HYPHEN_MINUS || last == HYPHEN_MINUS {
+                    return false;
+                }
+            } else if code_points.starts_with(&[0x78, 0x6E, HYPHEN_MINUS, HYPHEN_MINUS]) {
+                return false;
+            }
+
+            if code_points.contains(&FULL_STOP) || is_combining_mark(first) {
+                return false;
+            }
+
+            let statuses_valid = code_points.iter().all(|code| match idna_status(*code) {
+                IdnaStatus::Valid => true,
+                IdnaStatus::Deviation => !transitional,
+                IdnaStatus::DisallowedStd3Valid => !options.use_std3_ascii_rules,
+                _ => false,
+            });
+            if !statuses_valid {
+                return false;
+            }
+
+            if options.check_joiners {
+                let joiners_permitted = (0..label.len())
+                    .filter(|index| matches!(label[*index], 0x200C | 0x200D))
+                    .all(|index| is_joiner_permitted(label, index));
+                if !joiners_permitted {
+                    return false;
+                }
+            }
+
+            !(options.check_bidi && bidi_domain_name) || satisfies_bidi_rule(&code_points)
+        }
+
+        /// Apply the processing steps of UTS #46 section 4 to the domain name
+        /// `text` -- mapping, normalization to NFC, breaking into labels,
+        /// decoding of Punycode labels, and validation of each label --
+        /// appending the result to `output`.  Return false if an error was
+        /// recorded; processing continues past errors, as the standard
+        /// requires, leaving a label that can't be decoded unchanged.
+        ///
+        /// This is the ToUnicode operation of section 4.3.
+        ///
+        /// ```text
+        /// let options = IdnaOptions {
+        ///     transitional: false,
+        ///     use_std3_ascii_rules: true,
+        ///     check_hyphens: true,
+        ///     check_bidi: true,
+        ///     check_joiners: true,
+        /// };
+        ///
+        /// let text: Vec<u16> = "Cafe\u{301}.xn--caf-dma".encode_utf16().collect();
+        /// let mut output = vec![];
+        /// assert!(idna_process(&text, options, &mut output));
+        /// assert_eq!(String::from_utf16(&output).unwrap(), "café.café");
+        ///
+        /// // A label can't begin with a hyphen.
+        /// let text: Vec<u16> = "-a.b".encode_utf16().collect();
+        /// assert!(!idna_process(&text, options, &mut vec![]));
+        /// ```
+        pub fn idna_process(text: &[u16], options: IdnaOptions, output: &mut Vec<u16>) -> bool {
+            const FULL_STOP: u16 = 0x2E;
+            const ACE_PREFIX: [u16; 4] = [0x78, 0x6E, 0x2D, 0x2D]; // "xn--"
+
+            let mut mapped = vec![];
+            let mut valid = idna_map(
+                text,
+                options.transitional,
+                options.use_std3_ascii_rules,
+                &mut mapped,
+            );
+
+            // Each label, and whether it's validated under transitional
+            // processing, or `None` if it's already in error.
+            let mut labels: Vec<(Vec<u16>, Option<bool>)> = vec![];
+            for label in to_nfc(&mapped).split(|unit| *unit == FULL_STOP) {
+                let encoded = match label.strip_prefix(&ACE_PREFIX[..]) {
+                    Some(encoded) => encoded,
+                    None => {
+                        labels.push((label.to_vec(), Some(options.transitional)));
+                        continue;
+                    }
+                };
+
+                // A decoded label mustn't be empty or entirely ASCII.
+                let decoded = decode_punycode(encoded)
+                    .filter(|decoded| decoded.iter().any(|code| *code >= 0x80));
+                match decoded {
+                    Some(decoded) => {
+                        let mut units = vec![];
+                        for code in decoded {
+                            push_utf16_code_point(&mut units, code);
+                        }
+                        labels.push((units, Some(false)));
+                    }
+                    None => {
+                        valid = false;
+                        labels.push((label.to_vec(), None));
+                    }
+                }
+            }
+
+            // RFC 5893 section 1.4: a Bidi domain name contains a code point
+            // whose Bidi_Class is R, AL or AN.
+            let bidi_domain_name = options.check_bidi
+                && labels.iter().any(|(label, _)| {
+                    utf16_code_points(label).any(|code| {
+                        matches!(bidi_class(code), BidiClass::R | BidiClass::Al | BidiClass::An)
+                    })
+                });
+
+            for (index, (label, transitional)) in labels.iter().enumerate() {
+                if index > 0 {
+                    output.push(FULL_STOP);
+                }
+                if let Some(transitional) = transitional {
+                    valid &= is_valid_idna_label(label, *transitional, options, bidi_domain_name);
+                }
+                output.extend_from_slice(label);
+            }
+
+            valid
+        }
+    }
+}
@@ -6,7 +6,7 @@ use quote::quote;
 use unicode_info::types::NumericType;
 
 fn generate_index_table(
-    index: &Vec<u32>,
+    index: &[u32],
     elem_type: NumericType,
     index_name: &str,
 ) -> proc_macro2::TokenStream {
@@ -35,7 +35,7 @@ fn generate_index_table(
             quote! { #( #elems ),* }
         }
         NumericType::U32 => {
-            let elems: Vec<u32> = index.iter().map(|v| *v as u32).collect();
+            let elems: Vec<u32> = index.to_vec();
             quote! { #( #elems ),* }
         }
     };
@@ -49,15 +49,15 @@ fn generate_index_table(
 }
 
 pub fn generate_index_tables(
-    index1: &Vec<u32>,
+    index1: &[u32],
     index1_elem_type: NumericType,
     index1_name: &str,
-    index2: &Vec<u32>,
+    index2: &[u32],
     index2_elem_type: NumericType,
     index2_name: &str,
 ) -> proc_macro2::TokenStream {
-    let index1_code = generate_index_table(&index1, index1_elem_type, index1_name);
-    let index2_code = generate_index_table(&index2, index2_elem_type, index2_name);
+    let index1_code = generate_index_table(index1, index1_elem_type, index1_name);
+    let index2_code = generate_index_table(index2, index2_elem_type, index2_name);
 
     quote! {
         #index1_code
@@ -10,7 +10,7 @@ impl quote::ToTokens for IntRange {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let IntRange(start, end) = self;
         let code = quote! {
-            if #start <= code && code <= #end {
+            if (#start..=#end).contains(&code) {
                 return true;
             }
         };
@@ -26,7 +26,7 @@ pub struct IntRanges<'a> {
 }
 
 impl<'a> IntRanges<'a> {
-    fn new(code_points: &CodePointSet) -> IntRanges {
+    fn new(code_points: &CodePointSet) -> IntRanges<'_> {
         IntRanges {
             resume: None,
             nested_iter: code_points.iter(),
@@ -38,37 +38,35 @@ impl<'a> Iterator for IntRanges<'a> {
     type Item = IntRange;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let start = match self.resume {
-                Some(n) => {
-                    self.resume = None;
-                    n
-                }
-                None => *(self.nested_iter.next()?),
-            };
-
-            let mut curr = start;
-            loop {
-                let next = match self.nested_iter.next() {
-                    Some(n) => *n,
-                    None => {
-                        return Some(IntRange(start, curr));
-                    }
-                };
+        let start = match self.resume {
+            Some(n) => {
+                self.resume = None;
+                n
+            }
+            None => *(self.nested_iter.next()?),
+        };
 
-                if curr + 1 != next {
-                    self.resume = Some(next);
+        let mut curr = start;
+        loop {
+            let next = match self.nested_iter.next() {
+                Some(n) => *n,
+                None => {
                     return Some(IntRange(start, curr));
                 }
+            };
 
-                curr = next;
+            if curr + 1 != next {
+                self.resume = Some(next);
+                return Some(IntRange(start, curr));
             }
+
+            curr = next;
         }
     }
 }
 
 /// Given a set of code points, return an `IntRanges` that sequentially exposes
 /// all code points in the set as a series of `IntRange` ranges.
-pub fn int_ranges(code_points: &CodePointSet) -> IntRanges {
+pub fn int_ranges(code_points: &CodePointSet) -> IntRanges<'_> {
     IntRanges::new(code_points)
 }
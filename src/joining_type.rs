@@ -0,0 +1,206 @@
+//! Generate the Joining_Type and Joining_Group properties from
+//! ArabicShaping.txt, and a function checking whether ZERO WIDTH JOINER and
+//! ZERO WIDTH NON-JOINER appear in contexts in which UAX #31 permits them in
+//! identifiers.
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::range_table;
+use crate::ucd;
+use crate::ucd::UnicodeDataRecord;
+use quote::quote;
+use std::ops::RangeInclusive;
+
+const JOINING_TYPE: &str = "JoiningType";
+
+/// The `JoiningType` variant for each value in ArabicShaping.txt.
+const JOINING_TYPE_VALUES: [(&str, &str); 6] = [
+    ("U", "NonJoining"),
+    ("T", "Transparent"),
+    ("C", "JoinCausing"),
+    ("L", "LeftJoining"),
+    ("R", "RightJoining"),
+    ("D", "DualJoining"),
+];
+
+/// Compute the Joining_Type of every code point.  Code points not listed in
+/// ArabicShaping.txt are Transparent if their General_Category is Mn, Me or Cf,
+/// and Non_Joining otherwise.
+fn compute_joining_types(unicode_data: &[UnicodeDataRecord], contents: &str) -> Vec<PropertyValue> {
+    let mut values: Vec<PropertyValue> = ucd::general_categories(unicode_data)
+        .into_iter()
+        .map(|category| match category {
+            "Mn" | "Me" | "Cf" => PropertyValue::new(JOINING_TYPE, "Transparent"),
+            _ => PropertyValue::new(JOINING_TYPE, "NonJoining"),
+        })
+        .collect();
+
+    for fields in ucd::data_lines(contents) {
+        let code = ucd::parse_code_point(fields[0]);
+        let variant = property_enum::variant_for(&JOINING_TYPE_VALUES, fields[2]);
+        values[code as usize] = PropertyValue::new(JOINING_TYPE, variant);
+    }
+
+    values
+}
+
+/// Compute the ranges of code points in each Joining_Group other than
+/// No_Joining_Group, in code point order.
+fn compute_joining_group_ranges(contents: &str) -> Vec<(RangeInclusive<u32>, String)> {
+    let mut groups: Vec<(u32, &str)> = ucd::data_lines(contents)
+        .filter(|fields| fields[3] != "No_Joining_Group")
+        .map(|fields| (ucd::parse_code_point(fields[0]), fields[3]))
+        .collect();
+    groups.sort_unstable();
+
+    let mut ranges: Vec<(RangeInclusive<u32>, String)> = vec![];
+    for (code, group) in groups {
+        match ranges.last_mut() {
+            Some((range, last_group)) if *range.end() + 1 == code && *last_group == group => {
+                *range = *range.start()..=code;
+            }
+            _ => ranges.push((code..=code, group.to_string())),
+        }
+    }
+
+    ranges
+}
+
+/// Generate the `JoiningType` enum, the `joining_type` and `joining_group`
+/// functions and the tables backing them, and `is_joiner_permitted`.
+pub fn generate_joining_type_functions(
+    unicode_data: &[UnicodeDataRecord],
+) -> proc_macro2::TokenStream {
+    let contents = ucd::read_data_file("ArabicShaping.txt");
+
+    let joining_type_enum = property_enum::generate_property_enum(
+        JOINING_TYPE,
+        "A value of the Joining_Type property, as used in cursive joining and UAX #31.",
+        &JOINING_TYPE_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let joining_type_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(JoiningType),
+        "joiningtype",
+        "joining_type",
+        "Return the Joining_Type property of the provided code point.",
+        &compute_joining_types(unicode_data, &contents),
+    );
+
+    let joining_group_fn = range_table::generate_range_lookup(
+        quote!(&'static str),
+        "joining_group_ranges",
+        "joining_group",
+        r#"
+Return the Joining_Group property of the provided code point, as named in
+ArabicShaping.txt (e.g. "BEH"), or `None` if it's No_Joining_Group.
+"#
+        .trim(),
+        &compute_joining_group_ranges(&contents),
+    );
+
+    quote! {
+        #joining_type_enum
+
+        #joining_type_fn
+
+        #joining_group_fn
+
+        /// Return the Joining_Type of the code point before `index` in `text`
+        /// (if `before`) or at or after `index` (otherwise), skipping over
+        /// Transparent code points.
+        fn joining_type_skipping_transparent(
+            text: &[u16],
+            mut index: usize,
+            before: bool,
+        ) -> Option<JoiningType> {
+            loop {
+                let code = if before {
+                    if index == 0 {
+                        return None;
+                    }
+                    let (code, length) = decode_utf16_code_point_before(text, index);
+                    index -= length;
+                    code
+                } else {
+                    if index >= text.len() {
+                        return None;
+                    }
+                    let (code, length) = decode_utf16_code_point(text, index);
+                    index += length;
+                    code
+                };
+
+                match joining_type(code) {
+                    JoiningType::Transparent => continue,
+                    joining_type => return Some(joining_type),
+                }
+            }
+        }
+
+        /// Return true iff the code point immediately before `index` in `text`
+        /// is a virama (Canonical_Combining_Class=Virama).
+        fn virama_precedes(text: &[u16], index: usize) -> bool {
+            const VIRAMA: u8 = 9;
+
+            if index == 0 {
+                return false;
+            }
+            let (code, _) = decode_utf16_code_point_before(text, index);
+            canonical_combining_class(code) == VIRAMA
+        }
+
+        /// Return true iff the ZERO WIDTH JOINER or ZERO WIDTH NON-JOINER at
+        /// `index` in `text` appears in a context in which UAX #31 section 2.3
+        /// permits it within an identifier:
+        ///
+        /// - A1: ZWNJ between a left-joining or dual-joining code point and a
+        ///   right-joining or dual-joining one, each possibly separated from it
+        ///   by transparent code points;
+        /// - A2: ZWNJ immediately following a virama; or
+        /// - B: ZWJ immediately following a virama.
+        ///
+        /// Return false if `text[index]` isn't ZWJ or ZWNJ.
+        ///
+        /// ```text
+        /// // U+0628 ARABIC LETTER BEH, ZWNJ, U+0647 ARABIC LETTER HEH
+        /// assert!(is_joiner_permitted(&[0x0628, 0x200C, 0x0647], 1));
+        /// // U+0627 ARABIC LETTER ALEF (right-joining), ZWNJ, U+0647
+        /// assert!(!is_joiner_permitted(&[0x0627, 0x200C, 0x0647], 1));
+        /// // U+0915 DEVANAGARI LETTER KA, U+094D DEVANAGARI SIGN VIRAMA, ZWJ
+        /// assert!(is_joiner_permitted(&[0x0915, 0x094D, 0x200D], 2));
+        /// // ...but not if U+093C DEVANAGARI SIGN NUKTA intervenes.
+        /// assert!(!is_joiner_permitted(&[0x0915, 0x094D, 0x093C, 0x200D], 3));
+        /// ```
+        pub fn is_joiner_permitted(text: &[u16], index: usize) -> bool {
+            const ZWNJ: u16 = 0x200C;
+            const ZWJ: u16 = 0x200D;
+            use JoiningType as J;
+
+            match text[index] {
+                ZWNJ => {
+                    // A1
+                    let left = joining_type_skipping_transparent(text, index, true);
+                    let right = joining_type_skipping_transparent(text, index + 1, false);
+                    if matches!(left, Some(J::LeftJoining) | Some(J::DualJoining))
+                        && matches!(right, Some(J::RightJoining) | Some(J::DualJoining))
+                    {
+                        return true;
+                    }
+
+                    // A2
+                    virama_precedes(text, index)
+                }
+
+                // B
+                ZWJ => virama_precedes(text, index),
+
+                _ => false,
+            }
+        }
+    }
+}
@@ -1,17 +1,80 @@
 extern crate proc_macro;
 use quote::quote;
 
+mod age;
 mod ascii_tables;
+mod bidi;
+mod block;
+mod canonical_combining_class;
+mod canonical_composition;
+mod canonical_decomposition;
+mod case_change_flags;
+mod case_conversion;
+mod char_name;
+mod code_point_flags;
+mod code_point_lookup;
+mod code_point_mapping;
+mod confusables;
+mod east_asian_width;
 mod generate_table;
+mod grapheme_cluster_break;
+mod hangul;
+mod identifier_name;
+mod identifier_profile;
+mod identifier_security;
+mod idna_mapping;
 mod index_table;
 mod int_ranges;
+mod joining_type;
 mod latin1_tables;
+mod line_break;
+mod nfkc_casefold;
 mod non_bmp_case_functions;
+mod normalization_quick_check;
+mod numeric_value;
+mod property_enum;
+mod range_table;
+mod script;
+mod segmentation;
+mod sentence_break;
 mod special_casing_functions;
 mod supplemental_identifier_function;
-
+mod ucd;
+mod utf16_functions;
+mod word_break;
+
+use crate::age::generate_age_function;
+use crate::bidi::generate_bidi_functions;
+use crate::block::generate_block_function;
+use crate::canonical_combining_class::generate_canonical_combining_class_function;
+use crate::canonical_composition::generate_canonical_composition_functions;
+use crate::canonical_decomposition::generate_canonical_decomposition_functions;
+use crate::case_change_flags::generate_case_change_functions;
+use crate::case_conversion::generate_case_conversion_functions;
+use crate::char_name::generate_char_name_functions;
+use crate::code_point_flags::generate_code_point_flags_functions;
+use crate::confusables::generate_confusables_functions;
+use crate::east_asian_width::generate_east_asian_width_functions;
+use crate::grapheme_cluster_break::generate_grapheme_cluster_code;
+use crate::hangul::generate_hangul_functions;
+use crate::identifier_name::generate_identifier_name_functions;
+use crate::identifier_profile::generate_xid_functions;
+use crate::identifier_profile::parse_identifier_profile;
+use crate::identifier_profile::IdentifierProfile;
+use crate::identifier_security::generate_identifier_security_functions;
+use crate::idna_mapping::generate_idna_mapping_functions;
+use crate::joining_type::generate_joining_type_functions;
+use crate::line_break::generate_line_break_code;
+use crate::nfkc_casefold::generate_nfkc_casefold_functions;
 use crate::non_bmp_case_functions::generate_non_bmp_case_functions;
+use crate::normalization_quick_check::generate_normalization_quick_check_functions;
+use crate::numeric_value::generate_numeric_value_functions;
+use crate::script::generate_script_functions;
+use crate::segmentation::generate_segmentation_functions;
+use crate::sentence_break::generate_sentence_break_code;
 use crate::special_casing_functions::generate_special_casing_functions;
+use crate::utf16_functions::generate_utf16_functions;
+use crate::word_break::generate_word_break_code;
 use std::convert::TryFrom;
 use unicode_info::bmp;
 use unicode_info::bmp::CharacterInfo;
@@ -55,6 +118,8 @@ for code_point in 0..=0xFFFFu16 {
     let cinfo = charinfo[index2[index1_index_component + mask_component]];
 }
 ```
+
+The `char_info` function performs this lookup.
 "#
         .trim(),
         &table,
@@ -80,6 +145,20 @@ for code_point in 0..=0xFFFFu16 {
         // Index tables used to compute the index of the right CharacterInfo in
         // the info table.
         #info_index_tables
+
+        /// Return the `CharacterInfo` of the provided BMP code point.
+        ///
+        /// ```text
+        /// assert_eq!(char_info('A' as u16).lower_delta.0, 32);
+        /// ```
+        #[inline]
+        pub fn char_info(code: u16) -> &'static ::unicode_info::bmp::CharacterInfo {
+            let mask = (1usize << CHARINFO_SHIFT) - 1;
+            let index1_entry = charinfo_index1[(code >> CHARINFO_SHIFT) as usize] as usize;
+            let index2_entry =
+                charinfo_index2[(index1_entry << CHARINFO_SHIFT) + (code as usize & mask)];
+            &charinfo[index2_entry as usize]
+        }
     }
 }
 
@@ -111,6 +190,8 @@ for code_point in 0..=0xFFFFu16 {
     let delta = foldinfo[index2[index1_index_component + mask_component]];
 }
 ```
+
+The `fold_delta` function performs this lookup.
 "#
         .trim(),
         &data.bmp_folding_table,
@@ -136,6 +217,21 @@ for code_point in 0..=0xFFFFu16 {
         // Index tables used to compute the index of the right Delta in the
         // folding table.
         #folding_index_tables
+
+        /// Return the `Delta` to add (with wrapping) to the provided BMP code
+        /// point to determine the code point to which it case-folds.
+        ///
+        /// ```text
+        /// assert_eq!(fold_delta('A' as u16).0, 32);
+        /// ```
+        #[inline]
+        pub fn fold_delta(code: u16) -> ::unicode_info::case_folding::Delta {
+            let mask = (1usize << FOLDING_SHIFT) - 1;
+            let index1_entry = folding_index1[(code >> FOLDING_SHIFT) as usize] as usize;
+            let index2_entry =
+                folding_index2[(index1_entry << FOLDING_SHIFT) + (code as usize & mask)];
+            foldinfo[index2_entry as usize]
+        }
     }
 }
 
@@ -174,10 +270,61 @@ is 0xFFFF or lower.
         #is_identifier_start_fn
 
         #is_identifier_part_fn
+
+        /// Return true iff the provided code point is matched by the
+        /// ECMAScript IdentifierStart production (excluding Unicode escape
+        /// sequences), allowing it to appear at the start of an identifier.
+        ///
+        /// ```text
+        /// assert!(is_identifier_start('$' as u32));
+        /// assert!(!is_identifier_start('1' as u32));
+        /// ```
+        #[inline]
+        pub fn is_identifier_start(code: u32) -> bool {
+            if code < 0x80 {
+                isidstart[code as usize]
+            } else if code <= 0xFFFF {
+                char_info(code as u16).flags.is_unicode_id_start()
+            } else {
+                is_identifier_start_non_bmp(code)
+            }
+        }
+
+        /// Return true iff the provided code point is matched by the
+        /// ECMAScript IdentifierPart production (excluding Unicode escape
+        /// sequences), allowing it to appear within an identifier after its
+        /// starting character.  This includes ZERO WIDTH NON-JOINER and ZERO
+        /// WIDTH JOINER.
+        ///
+        /// ```text
+        /// assert!(is_identifier_part('1' as u32));
+        /// assert!(is_identifier_part(0x200D)); // ZERO WIDTH JOINER
+        /// assert!(!is_identifier_part('-' as u32));
+        /// ```
+        #[inline]
+        pub fn is_identifier_part(code: u32) -> bool {
+            const ZWNJ: u32 = 0x200C;
+            const ZWJ: u32 = 0x200D;
+
+            if code < 0x80 {
+                isident[code as usize]
+            } else if code <= 0xFFFF {
+                let flags = char_info(code as u16).flags;
+                flags.is_unicode_id_start()
+                    || flags.is_unicode_id_continue_only()
+                    || code == ZWNJ
+                    || code == ZWJ
+            } else {
+                is_identifier_part_non_bmp(code)
+            }
+        }
     }
 }
 
-fn generate_ascii_lookup_tables(bmp: &bmp::BMPInfo) -> proc_macro2::TokenStream {
+fn generate_ascii_lookup_tables(
+    bmp: &bmp::BMPInfo,
+    profile: IdentifierProfile,
+) -> proc_macro2::TokenStream {
     let index = &bmp.index;
     let table = &bmp.table;
 
@@ -231,10 +378,20 @@ matches either of the ECMAScript WhiteSpace or LineTerminator productions.
         &is_space,
     );
 
-    quote! {
-        #isidstart_table
+    // The XID equivalents of `isidstart` and `isident` are generated with the
+    // other XID tables.
+    let identifier_tables = if profile.includes_ecmascript() {
+        quote! {
+            #isidstart_table
 
-        #isident_table
+            #isident_table
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #identifier_tables
 
         #isspace_table
     }
@@ -266,13 +423,34 @@ point with value `i`.
 }
 
 #[proc_macro]
-pub fn generate_unicode_tables(_input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+pub fn generate_unicode_tables(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let profile = parse_identifier_profile(input.into());
+
+    // Tables not computed by unicode_info are generated from the Unicode data
+    // files, which must be present, and of the Unicode version unicode_info
+    // was generated from.
+    if let Err(message) = ucd::check_data_files() {
+        return quote! { compile_error!(#message); }.into();
+    }
+
     let cpt = code_point_table::generate_code_point_table();
     let dcp = derived_core_properties::process_derived_core_properties();
     let bmp = bmp::generate_bmp_info(&cpt, &dcp);
     let non_bmp = non_bmp::generate_non_bmp_info(&cpt);
+
+    if let Err(message) = ucd::check_data_version(&non_bmp.id_start_set) {
+        let data_file_dependencies = ucd::generate_data_file_dependencies();
+        return quote! {
+            compile_error!(#message);
+
+            #data_file_dependencies
+        }
+        .into();
+    }
+
     let cfd = case_folding::process_case_folding();
     let scd = special_casing::process_special_casing(&bmp);
+    let unicode_data = ucd::parse_unicode_data();
 
     // Character info table and two index tables.
     let charinfo_code = generate_charinfo_tables(&cpt, &dcp);
@@ -280,25 +458,129 @@ pub fn generate_unicode_tables(_input: proc_macro::TokenStream) -> proc_macro::T
     // Folding table and two index tables.
     let folding_code = generate_folding_tables(&cfd);
 
-    let isidentifier_start_part_code = generate_isidentifier_start_part_functions(&non_bmp);
+    // ECMAScript and/or XID identifier tables and non-BMP functions, as
+    // selected by the identifier profile.
+    let isidentifier_start_part_code = if profile.includes_ecmascript() {
+        generate_isidentifier_start_part_functions(&non_bmp)
+    } else {
+        quote! {}
+    };
+    let xid_code = if profile.includes_xid() {
+        generate_xid_functions()
+    } else {
+        quote! {}
+    };
 
     let special_casing_code = generate_special_casing_functions(&scd);
 
-    let ascii_lookup_code = generate_ascii_lookup_tables(&bmp);
+    let ascii_lookup_code = generate_ascii_lookup_tables(&bmp, profile);
 
     let latin1_lookup_code = generate_latin1_lookup_tables(&bmp);
 
     let non_bmp_case_functions = generate_non_bmp_case_functions(&non_bmp);
 
+    // Canonical_Combining_Class table, two index tables, and lookup function.
+    let combining_class_code = generate_canonical_combining_class_function(&unicode_data);
+
+    // Normalization quick-check enum, tables, and lookup functions, and a
+    // Latin-1 quick-check lookup table.
+    let quick_check_code = generate_normalization_quick_check_functions();
+
+    // Helper functions for generated functions that operate on UTF-16 text.
+    let utf16_code = generate_utf16_functions();
+
+    // Grapheme_Cluster_Break and related properties, and an iterator over
+    // extended grapheme cluster boundaries.
+    let grapheme_cluster_code = generate_grapheme_cluster_code();
+
+    // Word_Break and Sentence_Break properties, and iterators over word and
+    // sentence boundaries in UTF-16 and UTF-8 text.
+    let segmentation_code = generate_segmentation_functions();
+    let word_break_code = generate_word_break_code();
+    let sentence_break_code = generate_sentence_break_code();
+
+    // Line_Break property and resolved classes, the UAX #14 pair table, and an
+    // iterator over line break opportunities.
+    let line_break_code = generate_line_break_code(&unicode_data);
+
+    // East_Asian_Width property and terminal display widths.
+    let east_asian_width_code = generate_east_asian_width_functions(&unicode_data);
+
+    // Bidi_Class and the other properties used by the bidirectional
+    // algorithm.
+    let bidi_code = generate_bidi_functions(&unicode_data);
+
+    // Numeric_Type and Numeric_Value, and decimal digit values.
+    let numeric_value_code = generate_numeric_value_functions(&unicode_data);
+
+    // Hangul_Syllable_Type, and algorithmic Hangul syllable composition and
+    // decomposition.
+    let hangul_code = generate_hangul_functions();
+
+    // Character names, and lookup of code points by name.
+    let char_name_code = generate_char_name_functions(&unicode_data);
+
+    // Unicode version, and Age and Block range tables.
+    let age_code = generate_age_function();
+    let block_code = generate_block_function();
+
+    // Joining_Type and Joining_Group, and UAX #31 checks of joiner contexts.
+    let joining_type_code = generate_joining_type_functions(&unicode_data);
+
+    // Default-ignorable, noncharacter, private-use, surrogate and unassigned
+    // code point flags.
+    let code_point_flags_code = generate_code_point_flags_functions(&unicode_data);
+
+    // UTS #39 Identifier_Status and Identifier_Type.
+    let identifier_security_code = generate_identifier_security_functions();
+
+    // Canonical decomposition and NFD.
+    let canonical_decomposition_code = generate_canonical_decomposition_functions(&unicode_data);
+
+    // Canonical composition and NFC.
+    let canonical_composition_code = generate_canonical_composition_functions(&unicode_data);
+
+    // UTS #39 confusable prototypes and skeletons.
+    let confusables_code = generate_confusables_functions();
+
+    // Script and Script_Extensions, and UTS #39 mixed-script detection.
+    let script_code = generate_script_functions();
+
+    // UTS #46 IDNA mapping and processing.
+    let idna_mapping_code = generate_idna_mapping_functions(&unicode_data);
+
+    // NFKC_Casefold and Changes_When_NFKC_Casefolded.
+    let nfkc_casefold_code = generate_nfkc_casefold_functions();
+
+    // Changes_When_Lowercased and the other Changes_When_* case properties.
+    let case_change_code = generate_case_change_functions();
+
+    // Full uppercasing and lowercasing of UTF-16 text.
+    let case_conversion_code = generate_case_conversion_functions();
+
+    // ECMAScript IdentifierName validation and scanning of UTF-16 text.
+    let identifier_name_code = if profile.includes_ecmascript() {
+        generate_identifier_name_functions()
+    } else {
+        quote! {}
+    };
+
+    // Dependencies on the Unicode data files read above.
+    let data_file_dependencies = ucd::generate_data_file_dependencies();
+
     let code = quote! {
         /* Generated by the generate_unicode_tables! macro, DO NOT MODIFY */
 
+        #data_file_dependencies
+
         #charinfo_code
 
         #folding_code
 
         #isidentifier_start_part_code
 
+        #xid_code
+
         #special_casing_code
 
         // ASCII lookup tables:
@@ -311,6 +593,60 @@ pub fn generate_unicode_tables(_input: proc_macro::TokenStream) -> proc_macro::T
         #latin1_lookup_code
 
         #non_bmp_case_functions
+
+        #combining_class_code
+
+        #quick_check_code
+
+        #utf16_code
+
+        #grapheme_cluster_code
+
+        #segmentation_code
+
+        #word_break_code
+
+        #sentence_break_code
+
+        #line_break_code
+
+        #east_asian_width_code
+
+        #bidi_code
+
+        #numeric_value_code
+
+        #hangul_code
+
+        #char_name_code
+
+        #age_code
+
+        #block_code
+
+        #joining_type_code
+
+        #code_point_flags_code
+
+        #identifier_security_code
+
+        #canonical_decomposition_code
+
+        #canonical_composition_code
+
+        #confusables_code
+
+        #script_code
+
+        #idna_mapping_code
+
+        #nfkc_casefold_code
+
+        #case_change_code
+
+        #case_conversion_code
+
+        #identifier_name_code
     };
 
     code.into()
@@ -0,0 +1,852 @@
+//! Generate the Line_Break property, the resolved line breaking classes used
+//! by the line breaking algorithm of UAX #14, a table of break actions for
+//! every pair of resolved classes, and an iterator over the line break
+//! opportunities in UTF-16 text.
+
+use crate::code_point_lookup;
+use crate::east_asian_width;
+use crate::generate_table;
+use crate::grapheme_cluster_break;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::ucd;
+use crate::ucd::UnicodeDataRecord;
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::quote;
+
+const LINE_BREAK: &str = "LineBreak";
+const LINE_BREAK_ACTION: &str = "LineBreakAction";
+
+/// The `LineBreak` variant for each value in LineBreak.txt.
+const LINE_BREAK_VALUES: [(&str, &str); 48] = [
+    ("XX", "Xx"),
+    ("BK", "Bk"),
+    ("CR", "Cr"),
+    ("LF", "Lf"),
+    ("NL", "Nl"),
+    ("SP", "Sp"),
+    ("ZW", "Zw"),
+    ("CM", "Cm"),
+    ("ZWJ", "Zwj"),
+    ("WJ", "Wj"),
+    ("GL", "Gl"),
+    ("BA", "Ba"),
+    ("BB", "Bb"),
+    ("B2", "B2"),
+    ("HY", "Hy"),
+    ("CB", "Cb"),
+    ("CL", "Cl"),
+    ("CP", "Cp"),
+    ("EX", "Ex"),
+    ("IN", "In"),
+    ("NS", "Ns"),
+    ("OP", "Op"),
+    ("QU", "Qu"),
+    ("IS", "Is"),
+    ("NU", "Nu"),
+    ("PO", "Po"),
+    ("PR", "Pr"),
+    ("SY", "Sy"),
+    ("AI", "Ai"),
+    ("AL", "Al"),
+    ("CJ", "Cj"),
+    ("EB", "Eb"),
+    ("EM", "Em"),
+    ("H2", "H2"),
+    ("H3", "H3"),
+    ("HL", "Hl"),
+    ("ID", "Id"),
+    ("JL", "Jl"),
+    ("JV", "Jv"),
+    ("JT", "Jt"),
+    ("RI", "Ri"),
+    ("SA", "Sa"),
+    ("SG", "Sg"),
+    ("AK", "Ak"),
+    ("AP", "Ap"),
+    ("AS", "As"),
+    ("VF", "Vf"),
+    ("VI", "Vi"),
+];
+
+/// A line breaking class after resolution per rule LB1 (and tailored by
+/// East_Asian_Width for rule LB30), as used by the pair table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Class {
+    Bk,
+    Cr,
+    Lf,
+    Nl,
+    Sp,
+    Zw,
+    Cm,
+    Zwj,
+    Wj,
+    Gl,
+    Ba,
+    Bb,
+    B2,
+    Hy,
+    Cb,
+    Cl,
+    Cp,
+    CpEastAsian,
+    Ex,
+    In,
+    Ns,
+    Op,
+    OpEastAsian,
+    Qu,
+    QuPi,
+    QuPf,
+    Is,
+    Nu,
+    Po,
+    Pr,
+    Sy,
+    Al,
+    Eb,
+    Em,
+    H2,
+    H3,
+    Hl,
+    Id,
+    Jl,
+    Jv,
+    Jt,
+    Ri,
+    Ak,
+    Ap,
+    As,
+    Vf,
+    Vi,
+}
+
+use Class::*;
+
+/// All resolved classes, in order of their discriminants.
+const CLASSES: [Class; 47] = [
+    Bk,
+    Cr,
+    Lf,
+    Nl,
+    Sp,
+    Zw,
+    Cm,
+    Zwj,
+    Wj,
+    Gl,
+    Ba,
+    Bb,
+    B2,
+    Hy,
+    Cb,
+    Cl,
+    Cp,
+    CpEastAsian,
+    Ex,
+    In,
+    Ns,
+    Op,
+    OpEastAsian,
+    Qu,
+    QuPi,
+    QuPf,
+    Is,
+    Nu,
+    Po,
+    Pr,
+    Sy,
+    Al,
+    Eb,
+    Em,
+    H2,
+    H3,
+    Hl,
+    Id,
+    Jl,
+    Jv,
+    Jt,
+    Ri,
+    Ak,
+    Ap,
+    As,
+    Vf,
+    Vi,
+];
+
+impl quote::ToTokens for Class {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let variant = Ident::new(&format!("{:?}", self), Span::call_site());
+        tokens.extend(quote! { LineBreakClass::#variant });
+    }
+}
+
+impl Class {
+    /// Return the resolved class corresponding to the Line_Break property
+    /// variant `variant`, which mustn't be one that LB1 resolves to another
+    /// class.
+    fn from_variant(variant: &str) -> Class {
+        *CLASSES
+            .iter()
+            .find(|class| format!("{:?}", class) == variant)
+            .unwrap_or_else(|| panic!("unresolved line break class {}", variant))
+    }
+
+    fn is_op(self) -> bool {
+        matches!(self, Op | OpEastAsian)
+    }
+
+    fn is_cp(self) -> bool {
+        matches!(self, Cp | CpEastAsian)
+    }
+
+    fn is_qu(self) -> bool {
+        matches!(self, Qu | QuPi | QuPf)
+    }
+
+    fn is_ahletter(self) -> bool {
+        matches!(self, Al | Hl)
+    }
+
+    fn is_korean_syllable(self) -> bool {
+        matches!(self, Jl | Jv | Jt | H2 | H3)
+    }
+}
+
+/// Return true iff the rules of UAX #14 permit a line break between a code
+/// point of class `before` and one of class `after`, separated by one or more
+/// spaces if `spaces`.  `before` and `after` are never Sp, and rules involving
+/// more context than this are approximated as in the pair table implementation
+/// described in UAX #14.
+fn is_break_permitted(before: Class, after: Class, spaces: bool) -> bool {
+    // LB10: a combining mark not attached to a base character is treated as
+    // AL.  (Attached combining marks are handled by the pair table
+    // implementation itself.)
+    let before = if matches!(before, Cm | Zwj) {
+        Al
+    } else {
+        before
+    };
+
+    // LB7
+    if after == Zw {
+        return false;
+    }
+
+    // LB8
+    if before == Zw {
+        return true;
+    }
+
+    // LB11
+    if after == Wj || (before == Wj && !spaces) {
+        return false;
+    }
+
+    // LB12
+    if before == Gl && !spaces {
+        return false;
+    }
+
+    // LB12a
+    if after == Gl && !spaces && !matches!(before, Ba | Hy) {
+        return false;
+    }
+
+    // LB13
+    if after.is_cp() || matches!(after, Cl | Ex | Sy) {
+        return false;
+    }
+
+    // LB14
+    if before.is_op() {
+        return false;
+    }
+
+    // LB15a, LB15b and LB15c depend on the code points around the pair, and
+    // are applied by the iterator.
+
+    // LB15d
+    if after == Is {
+        return false;
+    }
+
+    // LB16
+    if (before == Cl || before.is_cp()) && after == Ns {
+        return false;
+    }
+
+    // LB17
+    if before == B2 && after == B2 {
+        return false;
+    }
+
+    // LB18
+    if spaces {
+        return true;
+    }
+
+    // LB19
+    if before.is_qu() || after.is_qu() {
+        return false;
+    }
+
+    // LB20
+    if before == Cb || after == Cb {
+        return true;
+    }
+
+    // LB21
+    if matches!(after, Ba | Hy | Ns) || before == Bb {
+        return false;
+    }
+
+    // LB21a depends on the code point before `before`, and is applied by the
+    // iterator.
+
+    // LB21b
+    if before == Sy && after == Hl {
+        return false;
+    }
+
+    // LB22
+    if after == In {
+        return false;
+    }
+
+    // LB23
+    if (before.is_ahletter() && after == Nu) || (before == Nu && after.is_ahletter()) {
+        return false;
+    }
+
+    // LB23a
+    if (before == Pr && matches!(after, Id | Eb | Em))
+        || (matches!(before, Id | Eb | Em) && after == Po)
+    {
+        return false;
+    }
+
+    // LB24
+    if (matches!(before, Pr | Po) && after.is_ahletter())
+        || (before.is_ahletter() && matches!(after, Pr | Po))
+    {
+        return false;
+    }
+
+    // LB25, approximated by pairs
+    if ((before == Cl || before.is_cp() || before == Nu) && matches!(after, Po | Pr))
+        || (matches!(before, Po | Pr) && (after.is_op() || after == Nu))
+        || (matches!(before, Hy | Is | Nu | Sy) && after == Nu)
+    {
+        return false;
+    }
+
+    // LB26
+    if (before == Jl && matches!(after, Jl | Jv | H2 | H3))
+        || (matches!(before, Jv | H2) && matches!(after, Jv | Jt))
+        || (matches!(before, Jt | H3) && after == Jt)
+    {
+        return false;
+    }
+
+    // LB27
+    if (before.is_korean_syllable() && after == Po) || (before == Pr && after.is_korean_syllable())
+    {
+        return false;
+    }
+
+    // LB28
+    if before.is_ahletter() && after.is_ahletter() {
+        return false;
+    }
+
+    // LB28a, approximated by pairs
+    if (before == Ap && matches!(after, Ak | As))
+        || (matches!(before, Ak | As) && matches!(after, Vf | Vi))
+    {
+        return false;
+    }
+
+    // LB29
+    if before == Is && after.is_ahletter() {
+        return false;
+    }
+
+    // LB30
+    if (matches!(before, Al | Hl | Nu) && after == Op)
+        || (before == Cp && matches!(after, Al | Hl | Nu))
+    {
+        return false;
+    }
+
+    // LB30a: the pair table forbids breaks between regional indicators, and
+    // the iterator permits them between pairs.
+    if before == Ri && after == Ri {
+        return false;
+    }
+
+    // LB30b
+    if before == Eb && after == Em {
+        return false;
+    }
+
+    // LB31
+    true
+}
+
+/// Return the `LineBreakAction` for a code point of class `after` following
+/// one of class `before`.
+fn pair_action(before: Class, after: Class) -> PropertyValue {
+    let action = |variant| PropertyValue::new(LINE_BREAK_ACTION, variant);
+
+    // LB9: combining marks attach to the preceding character, unless it's a
+    // space (LB10) or ZW (LB8).
+    if matches!(after, Cm | Zwj) {
+        return if before == Zw {
+            action("Direct")
+        } else if is_break_permitted(before, Al, true) {
+            action("CombiningIndirect")
+        } else {
+            action("CombiningProhibited")
+        };
+    }
+
+    match (
+        is_break_permitted(before, after, false),
+        is_break_permitted(before, after, true),
+    ) {
+        (true, true) => action("Direct"),
+        (false, true) => action("Indirect"),
+        (false, false) => action("Prohibited"),
+        (true, false) => panic!(
+            "break between {:?} and {:?} only without spaces",
+            before, after
+        ),
+    }
+}
+
+/// Compute the Line_Break property of every code point.
+fn compute_line_breaks() -> Vec<&'static str> {
+    let mut values = vec!["Xx"; ucd::CODE_POINT_LIMIT as usize];
+
+    for (range, value) in ucd::read_property_file_with_defaults("LineBreak.txt") {
+        let variant = property_enum::variant_for(&LINE_BREAK_VALUES, &value);
+        for code in range {
+            values[code as usize] = variant;
+        }
+    }
+
+    values
+}
+
+/// Compute the resolved line breaking class of every code point.
+fn compute_line_break_classes(
+    line_breaks: &[&'static str],
+    unicode_data: &[UnicodeDataRecord],
+) -> Vec<Class> {
+    let general_categories = ucd::general_categories(unicode_data);
+    let east_asian_widths = east_asian_width::compute_east_asian_widths();
+    let extended_pictographic = grapheme_cluster_break::compute_extended_pictographic();
+
+    line_breaks
+        .iter()
+        .enumerate()
+        .map(|(code, variant)| {
+            let general_category = general_categories[code];
+            match *variant {
+                // LB30b
+                _ if extended_pictographic[code] && general_category == "Cn" => Eb,
+
+                // LB1
+                "Ai" | "Sg" | "Xx" => Al,
+                "Sa" if matches!(general_category, "Mn" | "Mc") => Cm,
+                "Sa" => Al,
+                "Cj" => Ns,
+
+                // LB15a, LB15b
+                "Qu" if general_category == "Pi" => QuPi,
+                "Qu" if general_category == "Pf" => QuPf,
+
+                // LB30
+                "Op" if east_asian_width::is_east_asian(&east_asian_widths[code]) => OpEastAsian,
+                "Cp" if east_asian_width::is_east_asian(&east_asian_widths[code]) => CpEastAsian,
+
+                variant => Class::from_variant(variant),
+            }
+        })
+        .collect()
+}
+
+/// Generate the `LineBreak` and `LineBreakClass` enums, the `line_break` and
+/// `line_break_class` functions and the tables backing them, and the
+/// `LineBreakAction` pair table.
+fn generate_line_break_tables(unicode_data: &[UnicodeDataRecord]) -> proc_macro2::TokenStream {
+    let line_breaks = compute_line_breaks();
+    let classes = compute_line_break_classes(&line_breaks, unicode_data);
+
+    let line_break_enum = property_enum::generate_property_enum(
+        LINE_BREAK,
+        r#"
+A value of the Line_Break property.  Variants are named after the short aliases
+of the property values, e.g. `Al` for AL (Alphabetic).
+"#
+        .trim(),
+        &LINE_BREAK_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let class_names: Vec<String> = CLASSES.iter().map(|class| format!("{:?}", class)).collect();
+    let class_enum = property_enum::generate_property_enum(
+        "LineBreakClass",
+        r#"
+A line breaking class as used by the UAX #14 pair table: the Line_Break
+property of a code point resolved per rule LB1, with OP and CP split by whether
+the code point is East Asian (Fullwidth, Wide or Halfwidth) for rule LB30, QU
+split into initial (Pi) and final (Pf) quotation marks for rules LB15a and
+LB15b, and unassigned Extended_Pictographic code points resolved to EB for rule
+LB30b.
+"#
+        .trim(),
+        &class_names.iter().map(String::as_str).collect::<Vec<_>>(),
+    );
+
+    let action_enum = property_enum::generate_property_enum(
+        LINE_BREAK_ACTION,
+        r#"
+The action to take between two code points when determining line breaks, as in
+the UAX #14 pair table implementation.  `Direct` permits a break; `Indirect`
+permits one only if spaces intervene; `CombiningIndirect` and
+`CombiningProhibited` govern combining marks, which attach to the preceding
+character but are treated as AL after spaces, and which may be preceded by a
+break only in the former case; `Prohibited` never permits a break, even if
+spaces intervene.
+"#
+        .trim(),
+        &[
+            "Direct",
+            "Indirect",
+            "CombiningIndirect",
+            "CombiningProhibited",
+            "Prohibited",
+        ],
+    );
+
+    let line_break_values: Vec<_> = line_breaks
+        .iter()
+        .map(|variant| PropertyValue::new(LINE_BREAK, variant))
+        .collect();
+    let line_break_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(LineBreak),
+        "linebreak",
+        "line_break",
+        "Return the Line_Break property of the provided code point.",
+        &line_break_values,
+    );
+
+    let class_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(LineBreakClass),
+        "linebreakclass",
+        "line_break_class",
+        "Return the resolved line breaking class of the provided code point.",
+        &classes,
+    );
+
+    let n = CLASSES.len();
+    let pairs: Vec<_> = CLASSES
+        .iter()
+        .flat_map(|before| {
+            CLASSES
+                .iter()
+                .map(move |after| pair_action(*before, *after))
+        })
+        .collect();
+    let pair_table = generate_table::generate_table(
+        quote!(LineBreakAction),
+        "line_break_pairs",
+        r#"
+The UAX #14 pair table: the `LineBreakAction` for a code point of class `after`
+following one of class `before` is at index
+`before as usize * LINE_BREAK_CLASS_COUNT + after as usize`.
+"#
+        .trim(),
+        &pairs,
+    );
+
+    quote! {
+        #line_break_enum
+
+        #class_enum
+
+        #action_enum
+
+        #line_break_fn
+
+        #class_fn
+
+        /// The number of `LineBreakClass` variants.
+        const LINE_BREAK_CLASS_COUNT: usize = #n;
+
+        #pair_table
+    }
+}
+
+/// Generate `LineBreakOpportunities`, an iterator over the line break
+/// opportunities in UTF-16 text.
+fn generate_line_break_iterator() -> proc_macro2::TokenStream {
+    quote! {
+        /// A position at which a line may, or must, be broken.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct LineBreakOpportunity {
+            /// The index of the code unit before which the line may be broken.
+            pub index: usize,
+            /// True iff the line must be broken here, i.e. after a hard line
+            /// break or at the end of the text.
+            pub mandatory: bool,
+        }
+
+        /// An iterator over the line break opportunities in UTF-16 text, as
+        /// determined by the pair table implementation of the UAX #14 line
+        /// breaking algorithm.  The end of non-empty text is always exposed as
+        /// a mandatory break opportunity.  (The start of the text is never a
+        /// break opportunity.)
+        ///
+        /// ```text
+        /// let text: Vec<u16> = "a b\nc".encode_utf16().collect();
+        /// let opportunities: Vec<_> = LineBreakOpportunities::new(&text)
+        ///     .map(|opportunity| (opportunity.index, opportunity.mandatory))
+        ///     .collect();
+        /// assert_eq!(opportunities, vec![(2, false), (4, true), (5, true)]);
+        /// ```
+        pub struct LineBreakOpportunities<'a> {
+            text: &'a [u16],
+            position: usize,
+            /// The class determining the action at the next position, or
+            /// `None` at the start of the text or of a line after a mandatory
+            /// break.
+            context: Option<LineBreakClass>,
+            /// The class of the preceding code point.
+            prev: LineBreakClass,
+            /// The number of consecutive regional indicators preceding (LB30a).
+            regional_indicators: usize,
+            /// True iff the context is an initial quotation mark at the start
+            /// of a line or after OP, QU, GL, SP or ZW (LB15a).
+            opening_quote: bool,
+            /// True iff the context is HY or BA directly following HL (LB21a).
+            hebrew_hyphen: bool,
+        }
+
+        impl<'a> LineBreakOpportunities<'a> {
+            pub fn new(text: &'a [u16]) -> LineBreakOpportunities<'a> {
+                LineBreakOpportunities {
+                    text,
+                    position: 0,
+                    context: None,
+                    prev: LineBreakClass::Wj,
+                    regional_indicators: 0,
+                    opening_quote: false,
+                    hebrew_hyphen: false,
+                }
+            }
+
+            /// Return the class of the code point at the current position,
+            /// skipping combining marks, which attach to the code point before
+            /// them (LB9), or `None` at the end of the text.
+            fn next_class(&self) -> Option<LineBreakClass> {
+                let mut index = self.position;
+                while index < self.text.len() {
+                    let (code, length) = decode_utf16_code_point(self.text, index);
+                    match line_break_class(code) {
+                        LineBreakClass::Cm | LineBreakClass::Zwj => index += length,
+                        class => return Some(class),
+                    }
+                }
+                None
+            }
+        }
+
+        impl<'a> Iterator for LineBreakOpportunities<'a> {
+            type Item = LineBreakOpportunity;
+
+            fn next(&mut self) -> Option<LineBreakOpportunity> {
+                use LineBreakClass as C;
+
+                while self.position < self.text.len() {
+                    let index = self.position;
+                    let (code, length) = decode_utf16_code_point(self.text, index);
+                    let class = line_break_class(code);
+
+                    let context = match self.context {
+                        // LB2, LB9, LB10
+                        None => {
+                            self.position += length;
+                            self.context = Some(match class {
+                                C::Sp => C::Wj,
+                                C::Lf | C::Nl => C::Bk,
+                                C::Cm | C::Zwj => C::Al,
+                                class => class,
+                            });
+                            self.prev = class;
+                            self.regional_indicators = (class == C::Ri) as usize;
+                            self.opening_quote = class == C::QuPi;
+                            self.hebrew_hyphen = false;
+                            continue;
+                        }
+                        Some(context) => context,
+                    };
+
+                    // LB4, LB5
+                    if context == C::Bk || (context == C::Cr && class != C::Lf) {
+                        self.context = None;
+                        return Some(LineBreakOpportunity { index, mandatory: true });
+                    }
+
+                    self.position += length;
+
+                    // LB6, LB7
+                    match class {
+                        C::Sp => {
+                            self.prev = class;
+                            continue;
+                        }
+                        C::Bk | C::Lf | C::Nl => {
+                            self.context = Some(C::Bk);
+                            self.prev = class;
+                            self.opening_quote = false;
+                            self.hebrew_hyphen = false;
+                            continue;
+                        }
+                        C::Cr => {
+                            self.context = Some(C::Cr);
+                            self.prev = class;
+                            self.opening_quote = false;
+                            self.hebrew_hyphen = false;
+                            continue;
+                        }
+                        _ => {}
+                    }
+
+                    let after_spaces = self.prev == C::Sp;
+                    let action =
+                        line_break_pairs[context as usize * LINE_BREAK_CLASS_COUNT + class as usize];
+                    let (mut permitted, attached) = match action {
+                        LineBreakAction::Direct => (true, false),
+                        LineBreakAction::Indirect => (after_spaces, false),
+                        LineBreakAction::CombiningIndirect => (after_spaces, !after_spaces),
+                        LineBreakAction::CombiningProhibited => (false, !after_spaces),
+                        LineBreakAction::Prohibited => (false, false),
+                    };
+
+                    // LB15a
+                    if self.opening_quote {
+                        permitted = false;
+                    }
+
+                    // LB15b
+                    if class == C::QuPf
+                        && context != C::Zw
+                        && matches!(
+                            self.next_class(),
+                            None | Some(
+                                C::Sp
+                                    | C::Gl
+                                    | C::Wj
+                                    | C::Cl
+                                    | C::Qu
+                                    | C::QuPi
+                                    | C::QuPf
+                                    | C::Cp
+                                    | C::CpEastAsian
+                                    | C::Ex
+                                    | C::Is
+                                    | C::Sy
+                                    | C::Bk
+                                    | C::Cr
+                                    | C::Lf
+                                    | C::Nl
+                                    | C::Zw
+                            )
+                        )
+                    {
+                        permitted = false;
+                    }
+
+                    // LB15c, unless LB14 or LB15a forbids the break
+                    if class == C::Is
+                        && after_spaces
+                        && !matches!(context, C::Op | C::OpEastAsian)
+                        && !self.opening_quote
+                        && self.next_class() == Some(C::Nu)
+                    {
+                        permitted = true;
+                    }
+
+                    // LB21a, unless LB20 permits the break
+                    if self.hebrew_hyphen && !after_spaces && class != C::Cb {
+                        permitted = false;
+                    }
+
+                    // LB8a
+                    if self.prev == C::Zwj {
+                        permitted = false;
+                    }
+
+                    // LB30a
+                    if context == C::Ri && class == C::Ri && !after_spaces {
+                        permitted = self.regional_indicators % 2 == 0;
+                    }
+
+                    // A combining mark attached to a preceding character takes
+                    // on its class (LB9).
+                    if !attached {
+                        let opens_quote = matches!(
+                            context,
+                            C::Op | C::OpEastAsian | C::Qu | C::QuPi | C::QuPf | C::Gl | C::Zw
+                        );
+                        self.opening_quote = class == C::QuPi && (after_spaces || opens_quote);
+                        self.hebrew_hyphen =
+                            matches!(class, C::Hy | C::Ba) && context == C::Hl && !after_spaces;
+                        self.context = Some(class);
+                    }
+
+                    self.regional_indicators = match class {
+                        C::Ri => self.regional_indicators + 1,
+                        _ if attached => self.regional_indicators,
+                        _ => 0,
+                    };
+                    self.prev = class;
+
+                    if permitted {
+                        return Some(LineBreakOpportunity { index, mandatory: false });
+                    }
+                }
+
+                // LB3
+                if self.context.take().is_some() {
+                    return Some(LineBreakOpportunity {
+                        index: self.text.len(),
+                        mandatory: true,
+                    });
+                }
+
+                None
+            }
+        }
+    }
+}
+
+/// Generate the Line_Break property, the pair table, and the line break
+/// iterator.
+pub fn generate_line_break_code(unicode_data: &[UnicodeDataRecord]) -> proc_macro2::TokenStream {
+    let tables = generate_line_break_tables(unicode_data);
+    let iterator = generate_line_break_iterator();
+
+    quote! {
+        #tables
+
+        #iterator
+    }
+}
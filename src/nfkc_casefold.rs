@@ -0,0 +1,107 @@
+//! Generate the NFKC_Casefold (NFKC_CF) mapping and the
+//! Changes_When_NFKC_Casefolded property from DerivedNormalizationProps.txt,
+//! and a function applying the mapping to UTF-16 text.
+
+use crate::code_point_lookup;
+use crate::code_point_mapping;
+use crate::ucd;
+use quote::quote;
+use std::collections::BTreeMap;
+
+/// Read the NFKC_CF mapping of every code point listed in
+/// DerivedNormalizationProps.txt.  Every code point in a listed range has the
+/// same mapping, which may be empty.
+fn read_nfkc_casefold_mappings(contents: &str) -> BTreeMap<u32, Vec<u32>> {
+    let mut mappings = BTreeMap::new();
+    for fields in ucd::data_lines(contents) {
+        if fields[1] != "NFKC_CF" {
+            continue;
+        }
+
+        let mapping = ucd::parse_code_point_sequence(fields[2]);
+        for code in ucd::parse_code_point_range(fields[0]) {
+            mappings.insert(code, mapping.clone());
+        }
+    }
+
+    mappings
+}
+
+/// Generate `nfkc_casefold` and its tables, `changes_when_nfkc_casefolded`,
+/// and `to_nfkc_casefold`.
+pub fn generate_nfkc_casefold_functions() -> proc_macro2::TokenStream {
+    let contents = ucd::read_data_file("DerivedNormalizationProps.txt");
+
+    let nfkc_casefold_fn = code_point_mapping::generate_code_point_mapping(
+        "nfkccasefold",
+        "nfkc_casefold",
+        r#"
+Return the NFKC_Casefold (NFKC_CF) mapping of the provided code point, or `None`
+if it maps to itself.  Default-ignorable code points map to the empty sequence.
+
+```text
+assert_eq!(nfkc_casefold('A' as u32), Some(&['a' as u32][..]));
+assert_eq!(nfkc_casefold(0xAD), Some(&[][..])); // SOFT HYPHEN
+assert_eq!(nfkc_casefold('a' as u32), None);
+```
+"#
+        .trim(),
+        &read_nfkc_casefold_mappings(&contents),
+    );
+
+    let changes_when_nfkc_casefolded_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(bool),
+        "changeswhennfkccasefolded",
+        "changes_when_nfkc_casefolded",
+        r#"
+Return true iff the provided code point has the Changes_When_NFKC_Casefolded
+property: that is, applying toNFKC_Casefold to its NFD form changes it.
+"#
+        .trim(),
+        &ucd::read_binary_property(
+            "DerivedNormalizationProps.txt",
+            "Changes_When_NFKC_Casefolded",
+        ),
+    );
+
+    quote! {
+        #nfkc_casefold_fn
+
+        #changes_when_nfkc_casefolded_fn
+
+        /// Return `text` with every code point replaced by its NFKC_Casefold
+        /// mapping, in a single pass.  Code points that map to themselves,
+        /// including unpaired surrogates, are copied unchanged.
+        ///
+        /// For text in NFC, two strings with equal results are equivalent
+        /// identifiers under case-insensitive NFKC matching.  Full
+        /// toNFKC_Casefold additionally requires normalizing the result to
+        /// NFC, which is only necessary if a mapping ends in a character that
+        /// composes with the combining mark following it in `text`.
+        ///
+        /// ```text
+        /// // "Straße" -> "strasse"
+        /// assert_eq!(
+        ///     to_nfkc_casefold(&[0x53, 0x74, 0x72, 0x61, 0xDF, 0x65]),
+        ///     [0x73, 0x74, 0x72, 0x61, 0x73, 0x73, 0x65]
+        /// );
+        /// ```
+        pub fn to_nfkc_casefold(text: &[u16]) -> Vec<u16> {
+            let mut folded = Vec::with_capacity(text.len());
+            let mut index = 0;
+            while index < text.len() {
+                let (code, length) = decode_utf16_code_point(text, index);
+                match nfkc_casefold(code) {
+                    Some(mapping) => {
+                        for code in mapping {
+                            push_utf16_code_point(&mut folded, *code);
+                        }
+                    }
+                    None => folded.extend_from_slice(&text[index..index + length]),
+                }
+                index += length;
+            }
+            folded
+        }
+    }
+}
@@ -118,7 +118,7 @@ For a non-BMP code point whose UTF-16 decomposition consists of `lead` and
 
   let name = format_ident!("changes_when_{case}_cased_non_bmp", case = case);
 
-  let ranges = compute_conversion_ranges(&case_map);
+  let ranges = compute_conversion_ranges(case_map);
 
   let tests: Vec<_> = ranges
     .into_iter()
@@ -132,7 +132,7 @@ For a non-BMP code point whose UTF-16 decomposition consists of `lead` and
       } = range;
       let to_trail = trail + length - 1;
       quote! {
-        if lead == #lead && #trail <= trail && trail <= #to_trail {
+        if lead == #lead && (#trail..=#to_trail).contains(&trail) {
           return true;
         }
       }
@@ -196,7 +196,7 @@ different from `trail`.
 
   let name = format_ident!("to_{case}_case_non_bmp_trail", case = case);
 
-  let ranges = compute_conversion_ranges(&case_map);
+  let ranges = compute_conversion_ranges(case_map);
 
   let tests: Vec<_> = ranges
     .into_iter()
@@ -210,7 +210,7 @@ different from `trail`.
       } = range;
       let to_trail = trail + length - 1;
       quote! {
-        if lead == #lead && #trail <= trail && trail <= #to_trail {
+        if lead == #lead && (#trail..=#to_trail).contains(&trail) {
           return u16::wrapping_add(trail, #delta);
         }
       }
@@ -246,18 +246,95 @@ fn generate_to_case_non_bmp_trail_functions(
   }
 }
 
+/// Generate a `to_{upper,lower}_case` function that returns the simple
+/// {upper,lower}case mapping of any code point: BMP code points are mapped using
+/// the deltas in `charinfo`, and non-BMP code points using
+/// `to_{upper,lower}_case_non_bmp_trail`.
+fn generate_to_case(case: Casing, case_map: &types::CaseMap) -> proc_macro2::TokenStream {
+  let case = case.to_string();
+
+  // Non-BMP code points are reassembled from their own lead surrogate and the
+  // trailing unit of their cased form, so every mapping must keep the lead.
+  for (code, mapped) in case_map.iter() {
+    assert_eq!(
+      utf16_encode(*code).0,
+      utf16_encode(*mapped).0,
+      "to_{}_case assumes that U+{:04X} and its case mapping U+{:04X} share a lead \
+       surrogate",
+      case,
+      code,
+      mapped
+    );
+  }
+
+  let doc = format!(
+    r#"
+Return the simple {case}case mapping of the provided code point, or the code
+point itself if it has none.  Values greater than 0x10FFFF, which aren't code
+points, are returned unchanged.
+"#,
+    case = case
+  )
+  .trim()
+  .to_string();
+
+  let name = format_ident!("to_{case}_case", case = case);
+  let delta = format_ident!("{case}_delta", case = case);
+  let to_case_non_bmp_trail = format_ident!("to_{case}_case_non_bmp_trail", case = case);
+
+  quote! {
+    #[doc = #doc]
+    #[no_mangle]
+    #[inline]
+    pub extern "C" fn #name(code: u32) -> u32 {
+      if code <= 0xFFFF {
+        let code = code as u16;
+        return u16::wrapping_add(code, char_info(code).#delta.0) as u32;
+      }
+      if code > 0x10FFFF {
+        return code;
+      }
+
+      // Only the low ten bits of a non-BMP code point, which its trailing
+      // surrogate encodes, differ from those of its cased form.
+      let lead = (((code - 0x1_0000) >> 10) as u16) + 0xD800;
+      let trail = ((code & 0x3FF) as u16) + 0xDC00;
+      let cased_trail = #to_case_non_bmp_trail(lead, trail);
+      (code & !0x3FF) | ((cased_trail - 0xDC00) as u32)
+    }
+  }
+}
+
+/// Generate `to_{upper,lower}_case` functions.
+fn generate_to_case_functions(non_bmp: &non_bmp::NonBMPInfo) -> proc_macro2::TokenStream {
+  let to_upper_case_function = generate_to_case(Casing::Upper, &non_bmp.uppercase_map);
+
+  let to_lower_case_function = generate_to_case(Casing::Lower, &non_bmp.lowercase_map);
+
+  quote! {
+    #to_upper_case_function
+
+    #to_lower_case_function
+  }
+}
+
 /// Generate functions that indicate whether a non-BMP code point
 /// {upper,lower}cases to a different value and what the trailing code unit in
-/// the {upper,lower}cased form of a non-BMP code point will be.
+/// the {upper,lower}cased form of a non-BMP code point will be, and functions
+/// that {upper,lower}case any code point.
 pub fn generate_non_bmp_case_functions(non_bmp: &non_bmp::NonBMPInfo) -> proc_macro2::TokenStream {
   let changes_when_cased_non_bmp_functions =
-    generate_changes_when_cased_non_bmp_functions(&non_bmp);
+    generate_changes_when_cased_non_bmp_functions(non_bmp);
 
-  let to_case_non_bmp_trail_functions = generate_to_case_non_bmp_trail_functions(&non_bmp);
+  let to_case_non_bmp_trail_functions = generate_to_case_non_bmp_trail_functions(non_bmp);
+
+  let to_case_functions = generate_to_case_functions(non_bmp);
 
   quote! {
     #changes_when_cased_non_bmp_functions
 
     #to_case_non_bmp_trail_functions
+
+    #to_case_functions
   }
 }
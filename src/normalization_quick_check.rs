@@ -0,0 +1,137 @@
+//! Generate functions returning the NFC_QC, NFD_QC, NFKC_QC and NFKD_QC
+//! quick-check properties of code points, and a Latin-1 lookup table of the
+//! same information.
+
+use crate::code_point_lookup;
+use crate::latin1_tables;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::ucd;
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::quote;
+
+const QUICK_CHECK: &str = "QuickCheck";
+
+/// The four quick-check properties: the property name in
+/// DerivedNormalizationProps.txt, the name of the table storing its values, the
+/// name of the generated lookup function, and the bit set for it in
+/// `latin1_quick_check_table` when its value isn't `Yes`.
+const QUICK_CHECK_PROPERTIES: [(&str, &str, &str, &str); 4] = [
+    ("NFC_QC", "nfcqc", "nfc_quick_check", "QUICK_CHECK_NFC"),
+    ("NFD_QC", "nfdqc", "nfd_quick_check", "QUICK_CHECK_NFD"),
+    ("NFKC_QC", "nfkcqc", "nfkc_quick_check", "QUICK_CHECK_NFKC"),
+    ("NFKD_QC", "nfkdqc", "nfkd_quick_check", "QUICK_CHECK_NFKD"),
+];
+
+/// Compute the values of the quick-check property named `property` for every
+/// code point.  Code points not listed in DerivedNormalizationProps.txt have
+/// the value `Yes`.
+fn compute_quick_check_values(contents: &str, property: &str) -> Vec<PropertyValue> {
+    let mut values = vec![PropertyValue::new(QUICK_CHECK, "Yes"); ucd::CODE_POINT_LIMIT as usize];
+
+    for fields in ucd::data_lines(contents) {
+        if fields[1] != property {
+            continue;
+        }
+
+        let value = match fields[2] {
+            "N" => PropertyValue::new(QUICK_CHECK, "No"),
+            "M" => PropertyValue::new(QUICK_CHECK, "Maybe"),
+            v => panic!("unexpected {} value {:?}", property, v),
+        };
+
+        for code in ucd::parse_code_point_range(fields[0]) {
+            values[code as usize] = value.clone();
+        }
+    }
+
+    values
+}
+
+/// Generate the `QuickCheck` enum, the `nf{c,d,kc,kd}_quick_check` functions
+/// and the tables backing them, and `latin1_quick_check_table`.
+pub fn generate_normalization_quick_check_functions() -> proc_macro2::TokenStream {
+    let contents = ucd::read_data_file("DerivedNormalizationProps.txt");
+
+    let quick_check_enum = property_enum::generate_property_enum(
+        QUICK_CHECK,
+        r#"
+The value of a normalization quick-check property for a code point.  `Yes`
+means the code point can appear unchanged in a string in that normalization
+form; `No` means it can't; `Maybe` means it can only in some contexts, so that
+full normalization must be performed to decide.
+"#
+        .trim(),
+        &["Yes", "No", "Maybe"],
+    );
+
+    let mut lookups = vec![];
+    let mut latin1_bits = vec![0u8; 0x100];
+    let mut bit_constants = vec![];
+    for (bit, (property, table_name, fn_name, bit_name)) in
+        QUICK_CHECK_PROPERTIES.iter().enumerate()
+    {
+        let values = compute_quick_check_values(&contents, property);
+
+        let doc = format!(
+            r#"
+Return the {property} (quick-check) property of the provided code point.  If
+every code point in a string has value `QuickCheck::Yes`, and canonical
+combining classes are non-decreasing within each sequence of nonzero classes,
+then the string is already in the corresponding normalization form.
+"#,
+            property = property
+        )
+        .trim()
+        .to_string();
+
+        lookups.push(code_point_lookup::generate_code_point_lookup(
+            quote!(QuickCheck),
+            table_name,
+            fn_name,
+            &doc,
+            &values,
+        ));
+
+        let mask = 1u8 << bit;
+        for (code, bits) in latin1_bits.iter_mut().enumerate() {
+            if values[code] != PropertyValue::new(QUICK_CHECK, "Yes") {
+                *bits |= mask;
+            }
+        }
+
+        let bit_doc = format!(
+            "The bit set in `latin1_quick_check_table` for a code point whose {} isn't `Yes`.",
+            property
+        );
+        let bit_name = Ident::new(bit_name, Span::call_site());
+        bit_constants.push(quote! {
+            #[doc = #bit_doc]
+            pub const #bit_name: u8 = #mask;
+        });
+    }
+
+    let latin1_quick_check_table = latin1_tables::generate_latin1_table(
+        "latin1_quick_check_table",
+        r#"
+A lookup table storing at index `i` a bit set containing `QUICK_CHECK_NFC`,
+`QUICK_CHECK_NFD`, `QUICK_CHECK_NFKC` and `QUICK_CHECK_NFKD` for the
+normalization forms in which the code point with value `i` does not have
+quick-check value `Yes`.  For the very common case of Latin-1 text, a string
+whose code points all have entry 0 here is in all four normalization forms.
+        "#
+        .trim(),
+        &|code: u32| latin1_bits[code as usize],
+    );
+
+    quote! {
+        #quick_check_enum
+
+        #( #lookups )*
+
+        #( #bit_constants )*
+
+        #latin1_quick_check_table
+    }
+}
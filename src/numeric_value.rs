@@ -0,0 +1,161 @@
+//! Generate functions returning the Numeric_Type and Numeric_Value properties
+//! of code points, as given in UnicodeData.txt.
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::ucd;
+use crate::ucd::UnicodeDataRecord;
+use quote::quote;
+
+const NUMERIC_TYPE: &str = "NumericType";
+
+/// The numeric type and value of a code point, converted to tokens as a
+/// `NumericValue` literal.  Code points without a numeric value have numeric
+/// type `None` and value 0/1.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct NumericValue {
+    numeric_type: PropertyValue,
+    numerator: i64,
+    denominator: u32,
+}
+
+impl quote::ToTokens for NumericValue {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let NumericValue {
+            numeric_type,
+            numerator,
+            denominator,
+        } = self;
+        tokens.extend(quote! {
+            NumericValue {
+                numeric_type: #numeric_type,
+                numerator: #numerator,
+                denominator: #denominator,
+            }
+        });
+    }
+}
+
+/// Parse a numeric value in UnicodeData.txt, either an integer ("-5") or a
+/// fraction ("1/2"), into a numerator and denominator.
+fn parse_rational(s: &str) -> (i64, u32) {
+    let (numerator, denominator) = s.split_once('/').unwrap_or((s, "1"));
+    match (numerator.parse(), denominator.parse()) {
+        (Ok(numerator), Ok(denominator)) => (numerator, denominator),
+        _ => panic!("bad numeric value: {:?}", s),
+    }
+}
+
+#[test]
+fn check_parse_rational() {
+    assert_eq!(parse_rational("7"), (7, 1));
+    assert_eq!(parse_rational("-1/2"), (-1, 2));
+    assert_eq!(parse_rational("1000000000000"), (1_000_000_000_000, 1));
+}
+
+/// Compute the numeric type and value of every code point.
+fn compute_numeric_values(unicode_data: &[UnicodeDataRecord]) -> Vec<NumericValue> {
+    let none = NumericValue {
+        numeric_type: PropertyValue::new(NUMERIC_TYPE, "None"),
+        numerator: 0,
+        denominator: 1,
+    };
+    let mut values = vec![none; ucd::CODE_POINT_LIMIT as usize];
+
+    for record in unicode_data {
+        let numeric_value = match &record.numeric_value {
+            Some(numeric_value) => numeric_value,
+            None => continue,
+        };
+
+        let numeric_type = if record.decimal_digit_value.is_some() {
+            "Decimal"
+        } else if record.digit_value.is_some() {
+            "Digit"
+        } else {
+            "Numeric"
+        };
+
+        let (numerator, denominator) = parse_rational(numeric_value);
+        let value = NumericValue {
+            numeric_type: PropertyValue::new(NUMERIC_TYPE, numeric_type),
+            numerator,
+            denominator,
+        };
+        for code in record.range.clone() {
+            values[code as usize] = value.clone();
+        }
+    }
+
+    values
+}
+
+/// Generate the `NumericType` enum, the `NumericValue` struct, the
+/// `numeric_value` and `decimal_digit_value` functions, and the tables backing
+/// them.
+pub fn generate_numeric_value_functions(
+    unicode_data: &[UnicodeDataRecord],
+) -> proc_macro2::TokenStream {
+    let numeric_type_enum = property_enum::generate_property_enum(
+        NUMERIC_TYPE,
+        "A value of the Numeric_Type property.",
+        &["None", "Decimal", "Digit", "Numeric"],
+    );
+
+    let numeric_value_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(NumericValue),
+        "numericvalue",
+        "numeric_value",
+        r#"
+Return the Numeric_Type and Numeric_Value of the provided code point, as given
+in UnicodeData.txt.  (Numeric values of CJK ideographs given only in the Unihan
+database are not included.)
+
+```text
+let value = numeric_value(0x00BD); // VULGAR FRACTION ONE HALF
+assert_eq!(value.numeric_type, NumericType::Numeric);
+assert_eq!((value.numerator, value.denominator), (1, 2));
+```
+"#
+        .trim(),
+        &compute_numeric_values(unicode_data),
+    );
+
+    quote! {
+        #numeric_type_enum
+
+        /// The Numeric_Type of a code point, and its Numeric_Value as the
+        /// fraction `numerator / denominator`, in lowest terms.  Code points
+        /// without a numeric value have type `NumericType::None` and value
+        /// 0/1.
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct NumericValue {
+            pub numeric_type: NumericType,
+            pub numerator: i64,
+            pub denominator: u32,
+        }
+
+        #numeric_value_fn
+
+        /// Return the value of the provided code point if it's a decimal
+        /// digit (Numeric_Type=Decimal), i.e. a digit from 0 to 9 used in a
+        /// positional decimal number system, such as U+0661 ARABIC-INDIC DIGIT
+        /// ONE.
+        ///
+        /// ```text
+        /// assert_eq!(decimal_digit_value('7' as u32), Some(7));
+        /// assert_eq!(decimal_digit_value(0x0661), Some(1)); // ARABIC-INDIC DIGIT ONE
+        /// assert_eq!(decimal_digit_value(0x00B2), None); // SUPERSCRIPT TWO
+        /// ```
+        #[inline]
+        pub fn decimal_digit_value(code: u32) -> Option<u8> {
+            let value = numeric_value(code);
+            match value.numeric_type {
+                NumericType::Decimal => Some(value.numerator as u8),
+                _ => None,
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Generate enums whose variants are the values of an enumerated Unicode
+//! property, and refer to those variants in generated tables.
+
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::quote;
+
+/// A value of an enumerated property, converted to tokens as
+/// `EnumName::Variant`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PropertyValue {
+    enum_name: &'static str,
+    variant: &'static str,
+}
+
+impl PropertyValue {
+    pub fn new(enum_name: &'static str, variant: &'static str) -> PropertyValue {
+        PropertyValue { enum_name, variant }
+    }
+}
+
+impl quote::ToTokens for PropertyValue {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let enum_name = Ident::new(self.enum_name, Span::call_site());
+        let variant = Ident::new(self.variant, Span::call_site());
+        tokens.extend(quote! { #enum_name::#variant });
+    }
+}
+
+/// Return the enum variant for the property value `value`, given a list of
+/// `(value, variant)` pairs.
+pub fn variant_for(values: &[(&str, &'static str)], value: &str) -> &'static str {
+    values
+        .iter()
+        .find(|(v, _)| *v == value)
+        .unwrap_or_else(|| panic!("unexpected property value {:?}", value))
+        .1
+}
+
+/// Generate a fieldless `#[repr(u8)]` enum named `enum_name` with the given
+/// variants, in order.
+pub fn generate_property_enum(
+    enum_name: &str,
+    doc: &str,
+    variants: &[&str],
+) -> proc_macro2::TokenStream {
+    let enum_name = Ident::new(enum_name, Span::call_site());
+    let variants = variants
+        .iter()
+        .map(|variant| Ident::new(variant, Span::call_site()));
+
+    quote! {
+        #[doc = #doc]
+        #[repr(u8)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum #enum_name {
+            #( #variants ),*
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Generate a sorted table of code point ranges and their values, together with
+//! a function that looks up the value for a code point by binary search.  This
+//! suits properties whose values change only at a few points, such as Block.
+
+use crate::generate_table;
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::quote;
+use std::ops::RangeInclusive;
+
+/// Generate code to look up a value, of type `value_type`, for the code points
+/// in `ranges`, which must be sorted and non-overlapping.
+///
+/// The ranges are stored as `(start, end, value)` in a table named
+/// `table_name`.  A function named `fn_name`, documented by `doc`, accepts a
+/// code point and returns `Some(value)` for the range containing it, or `None`
+/// if no range contains it.
+pub fn generate_range_lookup<T>(
+    value_type: proc_macro2::TokenStream,
+    table_name: &str,
+    fn_name: &str,
+    doc: &str,
+    ranges: &[(RangeInclusive<u32>, T)],
+) -> proc_macro2::TokenStream
+where
+    T: quote::ToTokens,
+{
+    generate_lookup(value_type, table_name, fn_name, doc, ranges, true)
+}
+
+/// Generate code exactly as `generate_range_lookup` does, except that the
+/// lookup function is private.  This suits lookups that only back other
+/// generated functions.
+pub fn generate_private_range_lookup<T>(
+    value_type: proc_macro2::TokenStream,
+    table_name: &str,
+    fn_name: &str,
+    doc: &str,
+    ranges: &[(RangeInclusive<u32>, T)],
+) -> proc_macro2::TokenStream
+where
+    T: quote::ToTokens,
+{
+    generate_lookup(value_type, table_name, fn_name, doc, ranges, false)
+}
+
+fn generate_lookup<T>(
+    value_type: proc_macro2::TokenStream,
+    table_name: &str,
+    fn_name: &str,
+    doc: &str,
+    ranges: &[(RangeInclusive<u32>, T)],
+    public: bool,
+) -> proc_macro2::TokenStream
+where
+    T: quote::ToTokens,
+{
+    for pair in ranges.windows(2) {
+        assert!(
+            pair[0].0.end() < pair[1].0.start(),
+            "ranges out of order in {}",
+            table_name
+        );
+    }
+
+    let entries: Vec<_> = ranges
+        .iter()
+        .map(|(range, value)| {
+            let (start, end) = (range.start(), range.end());
+            quote!((#start, #end, #value))
+        })
+        .collect();
+
+    let table_doc = format!(
+        "The sorted code point ranges, and their values, searched by `{}`.",
+        fn_name
+    );
+    let table = generate_table::generate_table(
+        quote!((u32, u32, #value_type)),
+        table_name,
+        &table_doc,
+        &entries,
+    );
+
+    let table_name = Ident::new(table_name, Span::call_site());
+    let fn_name = Ident::new(fn_name, Span::call_site());
+    let visibility = if public { quote!(pub) } else { quote!() };
+
+    quote! {
+        #table
+
+        #[doc = #doc]
+        #visibility fn #fn_name(code: u32) -> Option<#value_type> {
+            let index = #table_name
+                .binary_search_by(|(start, end, _)| {
+                    if code < *start {
+                        core::cmp::Ordering::Greater
+                    } else if *end < code {
+                        core::cmp::Ordering::Less
+                    } else {
+                        core::cmp::Ordering::Equal
+                    }
+                })
+                .ok()?;
+            Some(#table_name[index].2)
+        }
+    }
+}
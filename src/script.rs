@@ -0,0 +1,357 @@
+//! Generate the Script and Script_Extensions properties, and the mixed-script
+//! detection defined by UTS #39, Unicode Security Mechanisms: augmented and
+//! resolved script sets, and restriction levels.
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::ucd;
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use quote::quote;
+use std::collections::BTreeMap;
+
+/// The scripts added by UTS #39 section 5.1 to augmented script sets, which
+/// aren't values of the Script property.
+const AUGMENTED_SCRIPTS: [&str; 3] = ["Hanb", "Jpan", "Kore"];
+
+/// The scripts, other than Latin, that may be mixed with Latin in a string of
+/// restriction level Moderately Restrictive: the Recommended scripts of UAX #31
+/// table 5, except Common, Inherited, Cyrillic and Greek.
+const MODERATELY_RESTRICTIVE_SCRIPTS: [&str; 26] = [
+    "Arab", "Armn", "Beng", "Bopo", "Deva", "Ethi", "Geor", "Gujr", "Guru", "Hang", "Hani", "Hebr",
+    "Hira", "Kana", "Khmr", "Knda", "Laoo", "Mlym", "Mymr", "Orya", "Sinh", "Taml", "Telu", "Thaa",
+    "Thai", "Tibt",
+];
+
+/// A value of the Script property, converted to tokens as `Script::Variant`,
+/// where the variant is the script's short name, e.g. `Script::Latn`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct ScriptValue(String);
+
+impl quote::ToTokens for ScriptValue {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let variant = Ident::new(&self.0, Span::call_site());
+        tokens.extend(quote! { Script::#variant });
+    }
+}
+
+/// A set of scripts, as a bit set of `1 << i` for the script with index `i`,
+/// converted to tokens as a `ScriptSet`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct ScriptSet(Vec<u64>);
+
+impl ScriptSet {
+    fn new(script_count: usize) -> ScriptSet {
+        ScriptSet(vec![0; script_count.div_ceil(64)])
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.0[index / 64] |= 1 << (index % 64);
+    }
+}
+
+impl quote::ToTokens for ScriptSet {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let words = &self.0;
+        tokens.extend(quote! { ScriptSet([#( #words ),*]) });
+    }
+}
+
+/// Read the short and long names of every Script value from
+/// PropertyValueAliases.txt, e.g. ("Latn", "Latin").
+fn read_script_names() -> Vec<(String, String)> {
+    let contents = ucd::read_data_file("PropertyValueAliases.txt");
+    ucd::data_lines(&contents)
+        .filter(|fields| fields[0] == "sc")
+        .map(|fields| (fields[1].to_string(), fields[2].to_string()))
+        .collect()
+}
+
+/// Compute the Script of every code point, as the short name of its value.
+fn compute_scripts(script_names: &[(String, String)]) -> Vec<ScriptValue> {
+    let short_names: BTreeMap<&str, &str> = script_names
+        .iter()
+        .map(|(short_name, long_name)| (long_name.as_str(), short_name.as_str()))
+        .collect();
+
+    let mut values = vec![ScriptValue("Zzzz".to_string()); ucd::CODE_POINT_LIMIT as usize];
+    for (range, value) in ucd::read_property_file("Scripts.txt") {
+        let short_name = short_names
+            .get(value.as_str())
+            .unwrap_or_else(|| panic!("unexpected Script value {:?}", value));
+        for code in range {
+            values[code as usize] = ScriptValue(short_name.to_string());
+        }
+    }
+
+    values
+}
+
+/// Compute the Script_Extensions of every code point.  Code points not listed
+/// in ScriptExtensions.txt have just their Script.
+fn compute_script_extensions(
+    scripts: &[ScriptValue],
+    script_indexes: &BTreeMap<&str, usize>,
+) -> Vec<ScriptSet> {
+    let script_index = |short_name: &str| {
+        *script_indexes
+            .get(short_name)
+            .unwrap_or_else(|| panic!("unexpected Script_Extensions value {:?}", short_name))
+    };
+
+    let mut values: Vec<ScriptSet> = scripts
+        .iter()
+        .map(|script| {
+            let mut set = ScriptSet::new(script_indexes.len());
+            set.insert(script_index(&script.0));
+            set
+        })
+        .collect();
+
+    for (range, value) in ucd::read_property_file("ScriptExtensions.txt") {
+        let mut set = ScriptSet::new(script_indexes.len());
+        for short_name in value.split_whitespace() {
+            set.insert(script_index(short_name));
+        }
+        for code in range {
+            values[code as usize] = set.clone();
+        }
+    }
+
+    values
+}
+
+/// Generate the `Script` enum, the `script` and `script_extensions` functions
+/// and the tables backing them, `ScriptSet`, `augmented_script_extensions`,
+/// `resolved_script_set`, the `RestrictionLevel` enum and `restriction_level`.
+pub fn generate_script_functions() -> proc_macro2::TokenStream {
+    let script_names = read_script_names();
+    let variants: Vec<&str> = script_names
+        .iter()
+        .map(|(short_name, _)| short_name.as_str())
+        .chain(AUGMENTED_SCRIPTS.iter().copied())
+        .collect();
+    let script_indexes: BTreeMap<&str, usize> = variants
+        .iter()
+        .enumerate()
+        .map(|(index, variant)| (*variant, index))
+        .collect();
+
+    let script_enum = property_enum::generate_property_enum(
+        "Script",
+        r#"
+A value of the Script property, named by its ISO 15924 code, e.g. `Latn` for
+Latin.  `Hanb`, `Jpan` and `Kore` are never the Script of a code point, but
+appear in augmented script sets.
+"#
+        .trim(),
+        &variants,
+    );
+
+    let scripts = compute_scripts(&script_names);
+    let script_extensions = compute_script_extensions(&scripts, &script_indexes);
+
+    let script_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(Script),
+        "scriptproperty",
+        "script",
+        "Return the Script property of the provided code point.",
+        &scripts,
+    );
+
+    let script_extensions_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(ScriptSet),
+        "scriptextensions",
+        "script_extensions",
+        r#"
+Return the Script_Extensions property of the provided code point: the scripts
+it's used with.  This is just its Script unless it's listed in
+ScriptExtensions.txt.
+"#
+        .trim(),
+        &script_extensions,
+    );
+
+    let script_set_of = |names: &[&str]| {
+        let mut set = ScriptSet::new(variants.len());
+        for name in names {
+            set.insert(script_indexes[name]);
+        }
+        set
+    };
+    let all_scripts = script_set_of(&variants);
+    let moderately_restrictive_scripts = script_set_of(&MODERATELY_RESTRICTIVE_SCRIPTS);
+    let highly_restrictive_scripts = script_set_of(&AUGMENTED_SCRIPTS);
+    let word_count = all_scripts.0.len();
+
+    let restriction_level_enum = property_enum::generate_property_enum(
+        "RestrictionLevel",
+        r#"
+A restriction level of a string, as defined by UTS #39 section 5.2, from most
+to least restrictive.
+"#
+        .trim(),
+        &[
+            "AsciiOnly",
+            "SingleScript",
+            "HighlyRestrictive",
+            "ModeratelyRestrictive",
+            "MinimallyRestrictive",
+            "Unrestricted",
+        ],
+    );
+
+    quote! {
+        #script_enum
+
+        #script_fn
+
+        /// A set of scripts, such as the Script_Extensions of a code point.
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct ScriptSet([u64; #word_count]);
+
+        impl ScriptSet {
+            /// The empty set.
+            pub const EMPTY: ScriptSet = ScriptSet([0; #word_count]);
+
+            /// The set of all scripts, including `Hanb`, `Jpan` and `Kore`.
+            pub const ALL: ScriptSet = #all_scripts;
+
+            /// Return true iff `script` is in this set.
+            #[inline]
+            pub fn contains(self, script: Script) -> bool {
+                let index = script as usize;
+                self.0[index / 64] & (1 << (index % 64)) != 0
+            }
+
+            /// Add `script` to this set.
+            #[inline]
+            pub fn insert(&mut self, script: Script) {
+                let index = script as usize;
+                self.0[index / 64] |= 1 << (index % 64);
+            }
+
+            /// Return the scripts in both this set and `other`.
+            #[inline]
+            pub fn intersection(self, other: ScriptSet) -> ScriptSet {
+                let mut words = self.0;
+                for (word, other_word) in words.iter_mut().zip(other.0.iter()) {
+                    *word &= other_word;
+                }
+                ScriptSet(words)
+            }
+
+            /// Return true iff this set contains no scripts.
+            #[inline]
+            pub fn is_empty(self) -> bool {
+                self.0.iter().all(|word| *word == 0)
+            }
+        }
+
+        #script_extensions_fn
+
+        /// Return the augmented script set of the provided code point, as
+        /// defined by UTS #39 section 5.1: its Script_Extensions, with `Hanb`,
+        /// `Jpan` and `Kore` added for the scripts they comprise, or
+        /// `ScriptSet::ALL` if its Script_Extensions is Common or Inherited.
+        ///
+        /// ```text
+        /// // U+3041 HIRAGANA LETTER SMALL A
+        /// assert!(augmented_script_extensions(0x3041).contains(Script::Jpan));
+        /// assert_eq!(augmented_script_extensions('1' as u32), ScriptSet::ALL);
+        /// ```
+        pub fn augmented_script_extensions(code: u32) -> ScriptSet {
+            let mut scripts = script_extensions(code);
+            if scripts.contains(Script::Zyyy) || scripts.contains(Script::Zinh) {
+                return ScriptSet::ALL;
+            }
+
+            if scripts.contains(Script::Hani) {
+                scripts.insert(Script::Hanb);
+                scripts.insert(Script::Jpan);
+                scripts.insert(Script::Kore);
+            }
+            if scripts.contains(Script::Hira) || scripts.contains(Script::Kana) {
+                scripts.insert(Script::Jpan);
+            }
+            if scripts.contains(Script::Hang) {
+                scripts.insert(Script::Kore);
+            }
+            if scripts.contains(Script::Bopo) {
+                scripts.insert(Script::Hanb);
+            }
+            scripts
+        }
+
+        /// Return the resolved script set of `text`, as defined by UTS #39
+        /// section 5.1: the intersection of the augmented script sets of its
+        /// code points.  `text` is single-script iff this isn't empty.
+        ///
+        /// ```text
+        /// // "a" followed by U+0430 CYRILLIC SMALL LETTER A
+        /// assert!(resolved_script_set(&[0x61, 0x430]).is_empty());
+        /// ```
+        pub fn resolved_script_set(text: &[u16]) -> ScriptSet {
+            utf16_code_points(text).fold(ScriptSet::ALL, |resolved, code| {
+                resolved.intersection(augmented_script_extensions(code))
+            })
+        }
+
+        #restriction_level_enum
+
+        /// Return the restriction level of the identifier `text`, as defined
+        /// by UTS #39 section 5.2.  Code points outside the identifier profile
+        /// are those without Identifier_Status=Allowed.
+        ///
+        /// ```text
+        /// assert_eq!(restriction_level(&[0x61, 0x62]), RestrictionLevel::AsciiOnly);
+        /// // "a" followed by U+0430 CYRILLIC SMALL LETTER A
+        /// assert_eq!(
+        ///     restriction_level(&[0x61, 0x430]),
+        ///     RestrictionLevel::MinimallyRestrictive
+        /// );
+        /// ```
+        pub fn restriction_level(text: &[u16]) -> RestrictionLevel {
+            const HIGHLY_RESTRICTIVE_SCRIPTS: ScriptSet = #highly_restrictive_scripts;
+            const MODERATELY_RESTRICTIVE_SCRIPTS: ScriptSet = #moderately_restrictive_scripts;
+
+            let mut ascii_only = true;
+            let mut resolved = ScriptSet::ALL;
+            // The resolved script set of the code points not used with Latin.
+            let mut resolved_without_latin = ScriptSet::ALL;
+            for code in utf16_code_points(text) {
+                if !is_identifier_allowed(code) {
+                    return RestrictionLevel::Unrestricted;
+                }
+
+                ascii_only &= code <= 0x7E;
+                let scripts = augmented_script_extensions(code);
+                resolved = resolved.intersection(scripts);
+                if !scripts.contains(Script::Latn) {
+                    resolved_without_latin = resolved_without_latin.intersection(scripts);
+                }
+            }
+
+            if ascii_only {
+                RestrictionLevel::AsciiOnly
+            } else if !resolved.is_empty() {
+                RestrictionLevel::SingleScript
+            } else if !resolved_without_latin
+                .intersection(HIGHLY_RESTRICTIVE_SCRIPTS)
+                .is_empty()
+            {
+                // Latin with Han and Bopomofo, Han and Hiragana and Katakana,
+                // or Han and Hangul.
+                RestrictionLevel::HighlyRestrictive
+            } else if !resolved_without_latin
+                .intersection(MODERATELY_RESTRICTIVE_SCRIPTS)
+                .is_empty()
+            {
+                RestrictionLevel::ModeratelyRestrictive
+            } else {
+                RestrictionLevel::MinimallyRestrictive
+            }
+        }
+    }
+}
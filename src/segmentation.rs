@@ -0,0 +1,146 @@
+//! Generate code shared by the word and sentence segmentation iterators, which
+//! operate upon both UTF-16 and UTF-8 text.
+
+use quote::quote;
+
+/// Generate the `SegmentableText` trait, its implementations for UTF-16 and
+/// UTF-8 text, and functions for moving over code points in such text while
+/// skipping ignored code points (as in UAX #29 rules WB4 and SB5).
+pub fn generate_segmentation_functions() -> proc_macro2::TokenStream {
+    quote! {
+        /// Text that can be segmented: UTF-16 text as `[u16]`, or UTF-8 text as
+        /// `str`.  Indexes into the text are code unit indexes.
+        trait SegmentableText {
+            /// The length of the text in code units.
+            fn length(&self) -> usize;
+
+            /// Return the code point starting at `index` and its length in code
+            /// units.
+            fn code_point_at(&self, index: usize) -> (u32, usize);
+
+            /// Return the code point ending just before `index` and its length
+            /// in code units.
+            fn code_point_before(&self, index: usize) -> (u32, usize);
+        }
+
+        impl SegmentableText for [u16] {
+            fn length(&self) -> usize {
+                self.len()
+            }
+
+            fn code_point_at(&self, index: usize) -> (u32, usize) {
+                decode_utf16_code_point(self, index)
+            }
+
+            fn code_point_before(&self, index: usize) -> (u32, usize) {
+                decode_utf16_code_point_before(self, index)
+            }
+        }
+
+        impl SegmentableText for str {
+            fn length(&self) -> usize {
+                self.len()
+            }
+
+            fn code_point_at(&self, index: usize) -> (u32, usize) {
+                let c = self[index..].chars().next().expect("index within text");
+                (c as u32, c.len_utf8())
+            }
+
+            fn code_point_before(&self, index: usize) -> (u32, usize) {
+                let c = self[..index].chars().next_back().expect("index within text");
+                (c as u32, c.len_utf8())
+            }
+        }
+
+        /// Return the class (as computed by `class`) of the code point before
+        /// `index` in `text` and the index at which that code point starts, or
+        /// `None` if `index` is the start of the text.
+        ///
+        /// Code points whose class satisfies `skip` are passed over, so that
+        /// the class of the code point preceding them is returned instead --
+        /// unless they follow the start of the text or a code point whose
+        /// class satisfies `stop`, in which case they aren't skipped.
+        fn segmentation_class_before<T, C>(
+            text: &T,
+            index: usize,
+            class: fn(u32) -> C,
+            skip: fn(C) -> bool,
+            stop: fn(C) -> bool,
+        ) -> Option<(C, usize)>
+        where
+            T: SegmentableText + ?Sized,
+            C: Copy,
+        {
+            if index == 0 {
+                return None;
+            }
+
+            let (code, length) = text.code_point_before(index);
+            let mut index = index - length;
+            let mut current = class(code);
+            while skip(current) && index > 0 {
+                let (code, length) = text.code_point_before(index);
+                let before = class(code);
+                if stop(before) {
+                    break;
+                }
+
+                current = before;
+                index -= length;
+            }
+
+            Some((current, index))
+        }
+
+        /// Return the class (as computed by `class`) of the first code point
+        /// at or after `index` whose class doesn't satisfy `skip`, and the index
+        /// just past it, or `None` if there's no such code point.
+        fn segmentation_class_after<T, C>(
+            text: &T,
+            mut index: usize,
+            class: fn(u32) -> C,
+            skip: fn(C) -> bool,
+        ) -> Option<(C, usize)>
+        where
+            T: SegmentableText + ?Sized,
+            C: Copy,
+        {
+            while index < text.length() {
+                let (code, length) = text.code_point_at(index);
+                index += length;
+
+                let current = class(code);
+                if !skip(current) {
+                    return Some((current, index));
+                }
+            }
+
+            None
+        }
+
+        /// Return the first boundary after `position` in `text`, as determined
+        /// by `is_boundary`, or `None` if `position` is the end of the text.
+        fn next_segmentation_boundary<T>(
+            text: &T,
+            position: usize,
+            is_boundary: fn(&T, usize) -> bool,
+        ) -> Option<usize>
+        where
+            T: SegmentableText + ?Sized,
+        {
+            if position >= text.length() {
+                return None;
+            }
+
+            let mut index = position;
+            loop {
+                let (_, length) = text.code_point_at(index);
+                index += length;
+                if is_boundary(text, index) {
+                    return Some(index);
+                }
+            }
+        }
+    }
+}
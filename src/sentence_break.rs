@@ -0,0 +1,299 @@
+//! Generate the Sentence_Break property and iterators over the sentence
+//! boundaries in UTF-16 and UTF-8 text as defined by UAX #29.
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::ucd;
+use quote::quote;
+
+const SENTENCE_BREAK: &str = "SentenceBreak";
+
+/// The `SentenceBreak` variant for each value in SentenceBreakProperty.txt.
+/// `Other` is the value of all unlisted code points.
+const SENTENCE_BREAK_VALUES: [(&str, &str); 15] = [
+    ("Other", "Other"),
+    ("CR", "Cr"),
+    ("LF", "Lf"),
+    ("Extend", "Extend"),
+    ("Sep", "Sep"),
+    ("Format", "Format"),
+    ("Sp", "Sp"),
+    ("Lower", "Lower"),
+    ("Upper", "Upper"),
+    ("OLetter", "OLetter"),
+    ("Numeric", "Numeric"),
+    ("ATerm", "ATerm"),
+    ("SContinue", "SContinue"),
+    ("STerm", "STerm"),
+    ("Close", "Close"),
+];
+
+fn compute_sentence_breaks() -> Vec<PropertyValue> {
+    let mut values =
+        vec![PropertyValue::new(SENTENCE_BREAK, "Other"); ucd::CODE_POINT_LIMIT as usize];
+
+    for (range, value) in ucd::read_property_file("SentenceBreakProperty.txt") {
+        let variant = property_enum::variant_for(&SENTENCE_BREAK_VALUES, &value);
+        for code in range {
+            values[code as usize] = PropertyValue::new(SENTENCE_BREAK, variant);
+        }
+    }
+
+    values
+}
+
+/// Generate the `SentenceBreak` enum, the `sentence_break` function and the
+/// tables backing it.
+fn generate_sentence_break_function() -> proc_macro2::TokenStream {
+    let sentence_break_enum = property_enum::generate_property_enum(
+        SENTENCE_BREAK,
+        "A value of the Sentence_Break property, as used in UAX #29.",
+        &SENTENCE_BREAK_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let sentence_break_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(SentenceBreak),
+        "sentencebreak",
+        "sentence_break",
+        "Return the Sentence_Break property of the provided code point.",
+        &compute_sentence_breaks(),
+    );
+
+    quote! {
+        #sentence_break_enum
+
+        #sentence_break_fn
+    }
+}
+
+/// Generate `is_sentence_boundary`, implementing the rules of UAX #29, and the
+/// `SentenceBoundaries` and `Utf8SentenceBoundaries` iterators.
+fn generate_sentence_boundary_iterators() -> proc_macro2::TokenStream {
+    quote! {
+        /// Return true iff code points of class `class` are ignored when
+        /// applying most sentence boundary rules (SB5).
+        fn is_sentence_ignorable(class: SentenceBreak) -> bool {
+            matches!(class, SentenceBreak::Extend | SentenceBreak::Format)
+        }
+
+        /// Return true iff `class` is ParaSep.
+        fn is_paragraph_separator(class: SentenceBreak) -> bool {
+            matches!(class, SentenceBreak::Sep | SentenceBreak::Cr | SentenceBreak::Lf)
+        }
+
+        /// Return the sentence break class of the code point before `index`,
+        /// in the sense of rule SB5, and the index at which it starts.
+        fn sentence_class_before<T>(text: &T, index: usize) -> Option<(SentenceBreak, usize)>
+        where
+            T: SegmentableText + ?Sized,
+        {
+            segmentation_class_before(
+                text,
+                index,
+                |code| sentence_break(code),
+                is_sentence_ignorable,
+                is_paragraph_separator,
+            )
+        }
+
+        /// If the text before `index` matches `SATerm Close* Sp*`, return the
+        /// SATerm class (STerm or ATerm) and whether any Sp were present.
+        fn sentence_terminator_before<T>(text: &T, index: usize) -> Option<(SentenceBreak, bool)>
+        where
+            T: SegmentableText + ?Sized,
+        {
+            let (mut class, mut start) = sentence_class_before(text, index)?;
+
+            let mut saw_space = false;
+            while class == SentenceBreak::Sp {
+                saw_space = true;
+                let (before, before_start) = sentence_class_before(text, start)?;
+                class = before;
+                start = before_start;
+            }
+
+            while class == SentenceBreak::Close {
+                let (before, before_start) = sentence_class_before(text, start)?;
+                class = before;
+                start = before_start;
+            }
+
+            match class {
+                SentenceBreak::STerm | SentenceBreak::ATerm => Some((class, saw_space)),
+                _ => None,
+            }
+        }
+
+        /// Return true iff the text starting at `index` matches
+        /// `( ¬(OLetter | Upper | Lower | ParaSep | SATerm) )* Lower` (SB8).
+        fn lower_follows<T>(text: &T, mut index: usize) -> bool
+        where
+            T: SegmentableText + ?Sized,
+        {
+            use SentenceBreak as S;
+
+            while let Some((class, next)) =
+                segmentation_class_after(text, index, |code| sentence_break(code), is_sentence_ignorable)
+            {
+                match class {
+                    S::Lower => return true,
+                    S::OLetter | S::Upper | S::Sep | S::Cr | S::Lf | S::STerm | S::ATerm => {
+                        return false
+                    }
+                    _ => index = next,
+                }
+            }
+
+            false
+        }
+
+        /// Return true iff there's a sentence boundary at `index` in `text`.
+        fn is_sentence_boundary<T>(text: &T, index: usize) -> bool
+        where
+            T: SegmentableText + ?Sized,
+        {
+            use SentenceBreak as S;
+
+            // SB1, SB2
+            if index == 0 || index == text.length() {
+                return true;
+            }
+
+            let (before_code, _) = text.code_point_before(index);
+            let (after_code, _) = text.code_point_at(index);
+            let before = sentence_break(before_code);
+            let next = sentence_break(after_code);
+
+            // SB3
+            if before == S::Cr && next == S::Lf {
+                return false;
+            }
+
+            // SB4
+            if is_paragraph_separator(before) {
+                return true;
+            }
+
+            // SB5
+            if is_sentence_ignorable(next) {
+                return false;
+            }
+
+            let (prev, prev_start) = sentence_class_before(text, index).expect("not at start");
+            let prev2 = sentence_class_before(text, prev_start).map(|(class, _)| class);
+
+            // SB6
+            if prev == S::ATerm && next == S::Numeric {
+                return false;
+            }
+
+            // SB7
+            if matches!(prev2, Some(S::Upper) | Some(S::Lower))
+                && prev == S::ATerm
+                && next == S::Upper
+            {
+                return false;
+            }
+
+            if let Some((terminator, saw_space)) = sentence_terminator_before(text, index) {
+                // SB8
+                if terminator == S::ATerm && lower_follows(text, index) {
+                    return false;
+                }
+
+                // SB8a
+                if matches!(next, S::SContinue | S::STerm | S::ATerm) {
+                    return false;
+                }
+
+                // SB9
+                if !saw_space && (next == S::Close || next == S::Sp || is_paragraph_separator(next)) {
+                    return false;
+                }
+
+                // SB10
+                if next == S::Sp || is_paragraph_separator(next) {
+                    return false;
+                }
+
+                // SB11
+                return true;
+            }
+
+            // SB998
+            false
+        }
+
+        /// An iterator over the sentence boundaries in UTF-16 text, as defined
+        /// by UAX #29.  Each boundary is exposed as the index of the code unit
+        /// just past the end of a sentence, so the last boundary is the length
+        /// of the text.  (The boundary at index 0 is not exposed.)
+        ///
+        /// ```text
+        /// let text: Vec<u16> = "Hi there. Bye.".encode_utf16().collect();
+        /// let boundaries: Vec<usize> = SentenceBoundaries::new(&text).collect();
+        /// assert_eq!(boundaries, vec![10, 14]);
+        /// ```
+        pub struct SentenceBoundaries<'a> {
+            text: &'a [u16],
+            position: usize,
+        }
+
+        impl<'a> SentenceBoundaries<'a> {
+            pub fn new(text: &'a [u16]) -> SentenceBoundaries<'a> {
+                SentenceBoundaries { text, position: 0 }
+            }
+        }
+
+        impl<'a> Iterator for SentenceBoundaries<'a> {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                let boundary =
+                    next_segmentation_boundary(self.text, self.position, is_sentence_boundary)?;
+                self.position = boundary;
+                Some(boundary)
+            }
+        }
+
+        /// An iterator over the sentence boundaries in UTF-8 text, exposed as
+        /// byte indexes, exactly as `SentenceBoundaries` does for UTF-16 text.
+        pub struct Utf8SentenceBoundaries<'a> {
+            text: &'a str,
+            position: usize,
+        }
+
+        impl<'a> Utf8SentenceBoundaries<'a> {
+            pub fn new(text: &'a str) -> Utf8SentenceBoundaries<'a> {
+                Utf8SentenceBoundaries { text, position: 0 }
+            }
+        }
+
+        impl<'a> Iterator for Utf8SentenceBoundaries<'a> {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                let boundary =
+                    next_segmentation_boundary(self.text, self.position, is_sentence_boundary)?;
+                self.position = boundary;
+                Some(boundary)
+            }
+        }
+    }
+}
+
+/// Generate the Sentence_Break property and sentence segmentation iterators.
+pub fn generate_sentence_break_code() -> proc_macro2::TokenStream {
+    let function = generate_sentence_break_function();
+    let iterators = generate_sentence_boundary_iterators();
+
+    quote! {
+        #function
+
+        #iterators
+    }
+}
@@ -14,7 +14,7 @@ fn in_any_range(ranges: Vec<int_ranges::IntRange>) -> Vec<proc_macro2::TokenStre
             }
         } else {
             quote! {
-                #start <= code && code <= #end
+                (#start..=#end).contains(&code)
             }
         }
     });
@@ -65,7 +65,7 @@ fn generate_accept_range(code_points: &CodePointSet) -> proc_macro2::TokenStream
 
 fn last_in_set(set: &CodePointSet) -> u32 {
     assert!(!set.is_empty());
-    *set.iter().rev().next().expect("non-empty set")
+    *set.iter().next_back().expect("non-empty set")
 }
 
 fn generate_changes_when_upper_cased_special_casing_fun(
@@ -78,7 +78,7 @@ fn generate_changes_when_upper_cased_special_casing_fun(
 
     let unconditional_code_points = unconditional_toupper
         .keys()
-        .map(|code| *code)
+        .copied()
         .collect::<Vec<u32>>();
 
     let lowest = unconditional_code_points[0];
@@ -159,7 +159,7 @@ fn generate_changes_when_upper_cased_special_casing_fun(
             // all special casing code points.  (Subsequent code depends upon
             // this to perform comparisons increasingly dependent on prior
             // comparisons having occurred.)
-            if code < #lowest || code > #highest {
+            if !(#lowest..=#highest).contains(&code) {
                 return false;
             }
 
@@ -179,7 +179,7 @@ fn generate_length_upper_case_special_casing_fun(
     // replacements of identical length together, group by replacement length,
     // then generate one match-arm per replacement length.
     let mut unconditional_toupper: Vec<(&u32, &Vec<u32>)> =
-        unconditional_toupper.into_iter().collect();
+        unconditional_toupper.iter().collect();
     unconditional_toupper
         .sort_by(|left, right| (left.1.len(), left.0).cmp(&(right.1.len(), right.0)));
 
@@ -230,12 +230,12 @@ fn generate_append_upper_case_special_casing_fun(
     unconditional_toupper: &special_casing::UnconditionalMapping,
 ) -> proc_macro2::TokenStream {
     let cases: Vec<proc_macro2::TokenStream> = unconditional_toupper
-        .into_iter()
+        .iter()
         .map(|(code, replacements)| {
             let code = *code as u16;
             let replacements_len = replacements.len();
             let replacements = replacements
-                .into_iter()
+                .iter()
                 .map(|code| *code as u16)
                 .collect::<Vec<u16>>();
 
@@ -0,0 +1,414 @@
+//! Read and parse files from the Unicode Character Database.
+//!
+//! Files are read from the directory named by the `UNICODE_DATA_DIR`
+//! environment variable, if it's set when the macro is expanded, and from the
+//! `data` directory in this crate otherwise.  fetch_unicode_data.sh downloads
+//! them.
+
+use quote::quote;
+use std::cell::RefCell;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use unicode_info::types::CodePointSet;
+
+/// One more than the greatest code point.
+pub const CODE_POINT_LIMIT: u32 = 0x11_0000;
+
+/// Every file read from the data directory, as downloaded by
+/// fetch_unicode_data.sh.
+const DATA_FILES: [&str; 25] = [
+    "ArabicShaping.txt",
+    "BidiBrackets.txt",
+    "BidiMirroring.txt",
+    "Blocks.txt",
+    "DerivedAge.txt",
+    "DerivedBidiClass.txt",
+    "DerivedCoreProperties.txt",
+    "DerivedNormalizationProps.txt",
+    "EastAsianWidth.txt",
+    "GraphemeBreakProperty.txt",
+    "HangulSyllableType.txt",
+    "IdentifierStatus.txt",
+    "IdentifierType.txt",
+    "IdnaMappingTable.txt",
+    "LineBreak.txt",
+    "NameAliases.txt",
+    "PropList.txt",
+    "PropertyValueAliases.txt",
+    "ScriptExtensions.txt",
+    "Scripts.txt",
+    "SentenceBreakProperty.txt",
+    "UnicodeData.txt",
+    "WordBreakProperty.txt",
+    "confusables.txt",
+    "emoji-data.txt",
+];
+
+thread_local! {
+    /// The paths of the data files read since the last call to
+    /// `generate_data_file_dependencies`.
+    static FILES_READ: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Return the path of the directory containing Unicode data files.
+fn data_directory() -> PathBuf {
+    match std::env::var_os("UNICODE_DATA_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("data"),
+    }
+}
+
+/// Return the contents of the Unicode data file with the given name, e.g.
+/// "UnicodeData.txt".  A leading byte order mark, which some files such as
+/// confusables.txt begin with, is removed.
+pub fn read_data_file(name: &str) -> String {
+    let path = data_directory().join(name);
+    let contents = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+
+    // `include_bytes!` resolves relative paths against the invoking file, so
+    // record an absolute one.
+    let path = fs::canonicalize(&path).unwrap_or(path);
+    FILES_READ.with(|files| {
+        let mut files = files.borrow_mut();
+        if !files.contains(&path) {
+            files.push(path);
+        }
+    });
+
+    strip_byte_order_mark(contents)
+}
+
+/// Return an error naming the data files missing from the data directory, if
+/// any are.
+pub fn check_data_files() -> Result<(), String> {
+    let directory = data_directory();
+    let missing: Vec<&str> = DATA_FILES
+        .iter()
+        .copied()
+        .filter(|name| !directory.join(name).is_file())
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    Err(format!(
+        "Unicode data files are missing from {}: {}.  Run fetch_unicode_data.sh to \
+         download them.",
+        directory.display(),
+        missing.join(", ")
+    ))
+}
+
+/// Return an error if the data directory holds a different version of the
+/// Unicode Character Database than the one `unicode_info` was generated from.
+/// The versions are compared by way of the non-BMP ID_Start code points, given
+/// by `unicode_info` as `id_start_set`, which every recent version of Unicode
+/// has added to.
+pub fn check_data_version(id_start_set: &CodePointSet) -> Result<(), String> {
+    let id_start = read_binary_property("DerivedCoreProperties.txt", "ID_Start");
+    let mismatch = (0x1_0000..CODE_POINT_LIMIT)
+        .find(|&code| id_start[code as usize] != id_start_set.contains(&code));
+    let code = match mismatch {
+        Some(code) => code,
+        None => return Ok(()),
+    };
+
+    // The first line of DerivedCoreProperties.txt names the file's version,
+    // e.g. "# DerivedCoreProperties-15.1.0.txt".
+    let contents = read_data_file("DerivedCoreProperties.txt");
+    let version = contents
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("# DerivedCoreProperties-"))
+        .and_then(|line| line.strip_suffix(".txt"))
+        .unwrap_or("unknown");
+
+    Err(format!(
+        "the Unicode data files in {} (version {}) don't match the Unicode version \
+         unicode_info was generated from: they disagree whether U+{:04X} is ID_Start.  \
+         Run fetch_unicode_data.sh with unicode_info's Unicode version.",
+        data_directory().display(),
+        version,
+        code
+    ))
+}
+
+/// Generate an `include_bytes!` of each data file read since this function was
+/// last called, and an `option_env!` of `UNICODE_DATA_DIR`, so that the crate
+/// invoking the macro is rebuilt when either changes.
+pub fn generate_data_file_dependencies() -> proc_macro2::TokenStream {
+    let paths: Vec<String> = FILES_READ.with(|files| {
+        files
+            .borrow_mut()
+            .drain(..)
+            .map(|path| path.display().to_string())
+            .collect()
+    });
+
+    quote! {
+        #( const _: &[u8] = include_bytes!(#paths); )*
+        const _: Option<&str> = option_env!("UNICODE_DATA_DIR");
+    }
+}
+
+/// Remove a leading byte order mark from the contents of a data file.
+fn strip_byte_order_mark(contents: String) -> String {
+    match contents.strip_prefix('\u{FEFF}') {
+        Some(rest) => rest.to_string(),
+        None => contents,
+    }
+}
+
+#[test]
+fn check_strip_byte_order_mark() {
+    let contents = "\u{FEFF}# confusables.txt\n05AD ;\t0596 ;\tMA\n".to_string();
+    let contents = strip_byte_order_mark(contents);
+    assert_eq!(contents, "# confusables.txt\n05AD ;\t0596 ;\tMA\n");
+    assert_eq!(
+        data_lines(&contents).collect::<Vec<_>>(),
+        [["05AD", "0596", "MA"]]
+    );
+
+    let contents = "0041;LATIN CAPITAL LETTER A".to_string();
+    assert_eq!(strip_byte_order_mark(contents.clone()), contents);
+}
+
+/// Parse a single hexadecimal code point, e.g. "00DF".
+pub fn parse_code_point(s: &str) -> u32 {
+    let code =
+        u32::from_str_radix(s.trim(), 16).unwrap_or_else(|_| panic!("bad code point: {:?}", s));
+    assert!(code < CODE_POINT_LIMIT, "code point out of range: {:?}", s);
+    code
+}
+
+/// Parse a code point range in UCD syntax: either a single code point "00DF",
+/// or an inclusive range "0041..005A".
+pub fn parse_code_point_range(s: &str) -> RangeInclusive<u32> {
+    match s.trim().split_once("..") {
+        Some((start, end)) => parse_code_point(start)..=parse_code_point(end),
+        None => {
+            let code = parse_code_point(s);
+            code..=code
+        }
+    }
+}
+
+/// Parse a sequence of space-separated hexadecimal code points, e.g.
+/// "0041 030A".
+pub fn parse_code_point_sequence(s: &str) -> Vec<u32> {
+    s.split_whitespace().map(parse_code_point).collect()
+}
+
+#[test]
+fn check_parse_code_point_range() {
+    assert_eq!(parse_code_point_range("00DF"), 0xDF..=0xDF);
+    assert_eq!(parse_code_point_range(" 0041..005A "), 0x41..=0x5A);
+    assert_eq!(parse_code_point_range("10000..10FFFF"), 0x10000..=0x10FFFF);
+}
+
+/// Iterate over the data lines in the contents of a UCD file, exposing each as
+/// its `;`-separated fields with surrounding whitespace trimmed.  Comments and
+/// empty lines are skipped.
+pub fn data_lines(contents: &str) -> impl Iterator<Item = Vec<&str>> {
+    contents.lines().filter_map(|line| {
+        let line = match line.find('#') {
+            Some(i) => &line[..i],
+            None => line,
+        };
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        Some(line.split(';').map(str::trim).collect())
+    })
+}
+
+/// Iterate over the `@missing` lines in the contents of a UCD file, which
+/// specify default values for code points not listed in the file, exposing each
+/// as its `;`-separated fields with surrounding whitespace trimmed.  For
+/// example,
+///
+/// ```text
+/// # @missing: 0000..10FFFF; XX
+/// ```
+///
+/// is exposed as `["0000..10FFFF", "XX"]`.
+pub fn missing_lines(contents: &str) -> impl Iterator<Item = Vec<&str>> {
+    contents.lines().filter_map(|line| {
+        let line = line.strip_prefix("# @missing:")?;
+        Some(line.split(';').map(str::trim).collect())
+    })
+}
+
+/// Read a UCD file consisting of lines of the form
+///
+/// ```text
+/// 0300..036F    ; Value # comment
+/// ```
+///
+/// and return every range in it along with the value in its second field.
+/// Code points not listed are left to the caller's default.
+pub fn read_property_file(name: &str) -> Vec<(RangeInclusive<u32>, String)> {
+    let contents = read_data_file(name);
+    parse_property_lines(name, data_lines(&contents))
+}
+
+/// Like `read_property_file`, but also return the ranges of the file's
+/// `@missing` lines, ahead of its data lines, so that assigning values in the
+/// order returned gives every code point its correct value.
+///
+/// Files such as LineBreak.txt and EastAsianWidth.txt give unlisted code
+/// points in some blocks (unassigned CJK ideographs, for example) a different
+/// default than everywhere else, and record those defaults only in `@missing`
+/// lines, so a single caller-supplied default can't reproduce them.
+pub fn read_property_file_with_defaults(name: &str) -> Vec<(RangeInclusive<u32>, String)> {
+    let contents = read_data_file(name);
+    parse_property_lines(name, missing_lines(&contents).chain(data_lines(&contents)))
+}
+
+/// Parse the range and value of each line of a property file.
+fn parse_property_lines<'a>(
+    name: &str,
+    lines: impl Iterator<Item = Vec<&'a str>>,
+) -> Vec<(RangeInclusive<u32>, String)> {
+    lines
+        .map(|fields| {
+            assert!(
+                fields.len() >= 2,
+                "malformed line in {}: {:?}",
+                name,
+                fields
+            );
+            (parse_code_point_range(fields[0]), fields[1].to_string())
+        })
+        .collect()
+}
+
+/// Read a UCD file listing the code points having binary properties, such as
+/// DerivedCoreProperties.txt, and return whether each code point has the
+/// property named `property`.
+pub fn read_binary_property(name: &str, property: &str) -> Vec<bool> {
+    let mut values = vec![false; CODE_POINT_LIMIT as usize];
+    for (range, value) in read_property_file(name) {
+        if value != property {
+            continue;
+        }
+
+        for code in range {
+            values[code as usize] = true;
+        }
+    }
+
+    values
+}
+
+/// A single entry in UnicodeData.txt.  Consecutive `<..., First>` and
+/// `<..., Last>` lines are combined into a single record covering the whole
+/// range.
+pub struct UnicodeDataRecord {
+    pub range: RangeInclusive<u32>,
+    /// The name field, e.g. "LATIN SMALL LETTER SHARP S" or "<control>".  For
+    /// a range the name is that of its first line with ", First" removed, e.g.
+    /// "<CJK Ideograph Extension A>".
+    pub name: String,
+    pub general_category: String,
+    pub canonical_combining_class: u8,
+    /// The tag of a compatibility decomposition (field 5), e.g. "<compat>", or
+    /// `None` if the decomposition is canonical or there isn't one.
+    pub decomposition_tag: Option<String>,
+    /// The decomposition mapping (field 5), empty if there isn't one.
+    pub decomposition: Vec<u32>,
+    /// The decimal digit value (field 6), if the code point is a decimal digit.
+    pub decimal_digit_value: Option<u8>,
+    /// The digit value (field 7), if the code point is a digit.
+    pub digit_value: Option<u8>,
+    /// The numeric value (field 8), e.g. "5" or "-1/2", if the code point has
+    /// one.
+    pub numeric_value: Option<String>,
+    pub bidi_mirrored: bool,
+}
+
+/// Parse a digit value field in UnicodeData.txt, which is empty if the code
+/// point has no such value.
+fn parse_optional_digit(s: &str) -> Option<u8> {
+    if s.is_empty() {
+        return None;
+    }
+
+    let digit = s
+        .parse()
+        .unwrap_or_else(|_| panic!("bad digit value: {:?}", s));
+    assert!(digit <= 9, "digit value out of range: {:?}", s);
+    Some(digit)
+}
+
+/// Read and parse UnicodeData.txt.  Records are in ascending code point order.
+pub fn parse_unicode_data() -> Vec<UnicodeDataRecord> {
+    let contents = read_data_file("UnicodeData.txt");
+
+    let mut records = vec![];
+    let mut range_start = None;
+    for fields in data_lines(&contents) {
+        assert_eq!(fields.len(), 15, "malformed UnicodeData.txt line");
+
+        let code = parse_code_point(fields[0]);
+        let name = fields[1];
+
+        if name.ends_with(", First>") {
+            assert!(range_start.is_none(), "nested First at {:04X}", code);
+            range_start = Some(code);
+            continue;
+        }
+
+        let (start, name) = match name.strip_suffix(", Last>") {
+            Some(range_name) => (
+                range_start.take().expect("Last without First"),
+                format!("{}>", range_name),
+            ),
+            None => {
+                assert!(range_start.is_none(), "First without Last");
+                (code, name.to_string())
+            }
+        };
+
+        let (decomposition_tag, decomposition) = match fields[5].strip_prefix('<') {
+            Some(_) => {
+                let (tag, mapping) = fields[5].split_once(' ').expect("bad decomposition");
+                (Some(tag.to_string()), mapping)
+            }
+            None => (None, fields[5]),
+        };
+
+        records.push(UnicodeDataRecord {
+            range: start..=code,
+            name,
+            general_category: fields[2].to_string(),
+            canonical_combining_class: fields[3].parse().expect("bad ccc"),
+            decomposition_tag,
+            decomposition: parse_code_point_sequence(decomposition),
+            decimal_digit_value: parse_optional_digit(fields[6]),
+            digit_value: parse_optional_digit(fields[7]),
+            numeric_value: Some(fields[8])
+                .filter(|value| !value.is_empty())
+                .map(str::to_string),
+            bidi_mirrored: fields[9] == "Y",
+        });
+    }
+
+    records
+}
+
+/// Return the General_Category of every code point, as its short alias, e.g.
+/// "Lu".  Code points not listed in UnicodeData.txt are "Cn" (Unassigned).
+pub fn general_categories(unicode_data: &[UnicodeDataRecord]) -> Vec<&str> {
+    let mut categories = vec!["Cn"; CODE_POINT_LIMIT as usize];
+    for record in unicode_data {
+        for code in record.range.clone() {
+            categories[code as usize] = &record.general_category;
+        }
+    }
+
+    categories
+}
@@ -0,0 +1,90 @@
+//! Generate functions for processing UTF-16 text, used by other generated
+//! functions that operate upon strings.
+
+use quote::quote;
+
+/// Generate `decode_utf16_code_point`, `decode_utf16_code_point_before`,
+/// `utf16_code_points` and `push_utf16_code_point`.
+pub fn generate_utf16_functions() -> proc_macro2::TokenStream {
+    quote! {
+        /// Decode the code point starting at `text[index]`, returning it and
+        /// the number of code units (1 or 2) that encode it.  An unpaired
+        /// surrogate decodes as itself.
+        ///
+        /// ```text
+        /// assert_eq!(decode_utf16_code_point(&[0x61, 0xD801, 0xDC00], 1), (0x10400, 2));
+        /// ```
+        #[inline]
+        pub fn decode_utf16_code_point(text: &[u16], index: usize) -> (u32, usize) {
+            let unit = text[index];
+            if (0xD800..=0xDBFF).contains(&unit) && index + 1 < text.len() {
+                let trail = text[index + 1];
+                if (0xDC00..=0xDFFF).contains(&trail) {
+                    let code = 0x10000 + (((unit as u32) - 0xD800) << 10) + ((trail as u32) - 0xDC00);
+                    return (code, 2);
+                }
+            }
+
+            (unit as u32, 1)
+        }
+
+        /// Decode the code point ending just before `text[index]`, returning it
+        /// and the number of code units (1 or 2) that encode it.  An unpaired
+        /// surrogate decodes as itself.
+        ///
+        /// ```text
+        /// assert_eq!(decode_utf16_code_point_before(&[0x61, 0xD801, 0xDC00], 3), (0x10400, 2));
+        /// ```
+        #[inline]
+        pub fn decode_utf16_code_point_before(text: &[u16], index: usize) -> (u32, usize) {
+            let unit = text[index - 1];
+            if (0xDC00..=0xDFFF).contains(&unit) && index >= 2 {
+                let lead = text[index - 2];
+                if (0xD800..=0xDBFF).contains(&lead) {
+                    let code = 0x10000 + (((lead as u32) - 0xD800) << 10) + ((unit as u32) - 0xDC00);
+                    return (code, 2);
+                }
+            }
+
+            (unit as u32, 1)
+        }
+
+        /// Iterate over the code points in `text`, decoding unpaired surrogates
+        /// as themselves.
+        ///
+        /// ```text
+        /// let code_points: Vec<u32> = utf16_code_points(&[0x61, 0xD801, 0xDC00]).collect();
+        /// assert_eq!(code_points, [0x61, 0x10400]);
+        /// ```
+        pub fn utf16_code_points(text: &[u16]) -> impl Iterator<Item = u32> + '_ {
+            let mut index = 0;
+            core::iter::from_fn(move || {
+                if index >= text.len() {
+                    return None;
+                }
+                let (code, length) = decode_utf16_code_point(text, index);
+                index += length;
+                Some(code)
+            })
+        }
+
+        /// Append the UTF-16 encoding of `code` (one or two code units) to
+        /// `text`.
+        ///
+        /// ```text
+        /// let mut text = vec![];
+        /// push_utf16_code_point(&mut text, 0x10400);
+        /// assert_eq!(text, [0xD801, 0xDC00]);
+        /// ```
+        #[inline]
+        pub fn push_utf16_code_point(text: &mut Vec<u16>, code: u32) {
+            if code < 0x10000 {
+                text.push(code as u16);
+            } else {
+                let code = code - 0x10000;
+                text.push(0xD800 + (code >> 10) as u16);
+                text.push(0xDC00 + (code & 0x3FF) as u16);
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Generate the Word_Break property and iterators over the word boundaries in
+//! UTF-16 and UTF-8 text as defined by UAX #29.
+
+use crate::code_point_lookup;
+use crate::property_enum;
+use crate::property_enum::PropertyValue;
+use crate::ucd;
+use quote::quote;
+
+const WORD_BREAK: &str = "WordBreak";
+
+/// The `WordBreak` variant for each value in WordBreakProperty.txt.  `Other` is
+/// the value of all unlisted code points.
+const WORD_BREAK_VALUES: [(&str, &str); 19] = [
+    ("Other", "Other"),
+    ("CR", "Cr"),
+    ("LF", "Lf"),
+    ("Newline", "Newline"),
+    ("Extend", "Extend"),
+    ("ZWJ", "Zwj"),
+    ("Regional_Indicator", "RegionalIndicator"),
+    ("Format", "Format"),
+    ("Katakana", "Katakana"),
+    ("Hebrew_Letter", "HebrewLetter"),
+    ("ALetter", "ALetter"),
+    ("Single_Quote", "SingleQuote"),
+    ("Double_Quote", "DoubleQuote"),
+    ("MidNumLet", "MidNumLet"),
+    ("MidLetter", "MidLetter"),
+    ("MidNum", "MidNum"),
+    ("Numeric", "Numeric"),
+    ("ExtendNumLet", "ExtendNumLet"),
+    ("WSegSpace", "WSegSpace"),
+];
+
+fn compute_word_breaks() -> Vec<PropertyValue> {
+    let mut values = vec![PropertyValue::new(WORD_BREAK, "Other"); ucd::CODE_POINT_LIMIT as usize];
+
+    for (range, value) in ucd::read_property_file("WordBreakProperty.txt") {
+        let variant = property_enum::variant_for(&WORD_BREAK_VALUES, &value);
+        for code in range {
+            values[code as usize] = PropertyValue::new(WORD_BREAK, variant);
+        }
+    }
+
+    values
+}
+
+/// Generate the `WordBreak` enum, the `word_break` function and the tables
+/// backing it.
+fn generate_word_break_function() -> proc_macro2::TokenStream {
+    let word_break_enum = property_enum::generate_property_enum(
+        WORD_BREAK,
+        "A value of the Word_Break property, as used in UAX #29.",
+        &WORD_BREAK_VALUES
+            .iter()
+            .map(|(_, variant)| *variant)
+            .collect::<Vec<_>>(),
+    );
+
+    let word_break_fn = code_point_lookup::generate_code_point_lookup(
+        quote!(WordBreak),
+        "wordbreak",
+        "word_break",
+        "Return the Word_Break property of the provided code point.",
+        &compute_word_breaks(),
+    );
+
+    quote! {
+        #word_break_enum
+
+        #word_break_fn
+    }
+}
+
+/// Generate `is_word_boundary`, implementing the rules of UAX #29, and the
+/// `WordBoundaries` and `Utf8WordBoundaries` iterators.
+fn generate_word_boundary_iterators() -> proc_macro2::TokenStream {
+    quote! {
+        /// Return true iff code points of class `class` are ignored when
+        /// applying most word boundary rules (WB4).
+        fn is_word_ignorable(class: WordBreak) -> bool {
+            matches!(class, WordBreak::Extend | WordBreak::Format | WordBreak::Zwj)
+        }
+
+        /// Return true iff `class` is Newline, CR or LF (WB3a, WB3b).
+        fn is_word_newline(class: WordBreak) -> bool {
+            matches!(class, WordBreak::Newline | WordBreak::Cr | WordBreak::Lf)
+        }
+
+        /// Return true iff `class` is AHLetter.
+        fn is_ahletter(class: WordBreak) -> bool {
+            matches!(class, WordBreak::ALetter | WordBreak::HebrewLetter)
+        }
+
+        /// Return true iff `class` is MidNumLetQ.
+        fn is_mid_num_let_q(class: WordBreak) -> bool {
+            matches!(class, WordBreak::MidNumLet | WordBreak::SingleQuote)
+        }
+
+        /// Return the word break class of the code point before `index`, in the
+        /// sense of rule WB4, and the index at which it starts.
+        fn word_class_before<T>(text: &T, index: usize) -> Option<(WordBreak, usize)>
+        where
+            T: SegmentableText + ?Sized,
+        {
+            segmentation_class_before(text, index, |code| word_break(code), is_word_ignorable, is_word_newline)
+        }
+
+        /// Return true iff there's a word boundary at `index` in `text`.
+        fn is_word_boundary<T>(text: &T, index: usize) -> bool
+        where
+            T: SegmentableText + ?Sized,
+        {
+            use WordBreak as W;
+
+            // WB1, WB2
+            if index == 0 || index == text.length() {
+                return true;
+            }
+
+            let (before_code, _) = text.code_point_before(index);
+            let (after_code, after_length) = text.code_point_at(index);
+            let before = word_break(before_code);
+            let next = word_break(after_code);
+
+            // WB3
+            if before == W::Cr && next == W::Lf {
+                return false;
+            }
+
+            // WB3a, WB3b
+            if is_word_newline(before) || is_word_newline(next) {
+                return true;
+            }
+
+            // WB3c
+            if before == W::Zwj && is_extended_pictographic(after_code) {
+                return false;
+            }
+
+            // WB3d
+            if before == W::WSegSpace && next == W::WSegSpace {
+                return false;
+            }
+
+            // WB4
+            if is_word_ignorable(next) {
+                return false;
+            }
+
+            let (prev, prev_start) = word_class_before(text, index).expect("not at start");
+            let prev2 = word_class_before(text, prev_start).map(|(class, _)| class);
+            let next2 = segmentation_class_after(
+                text,
+                index + after_length,
+                |code| word_break(code),
+                is_word_ignorable,
+            )
+            .map(|(class, _)| class);
+
+            let prev2_is = |test: fn(WordBreak) -> bool| prev2.map_or(false, test);
+            let next2_is = |test: fn(WordBreak) -> bool| next2.map_or(false, test);
+
+            // WB5
+            if is_ahletter(prev) && is_ahletter(next) {
+                return false;
+            }
+
+            // WB6
+            if is_ahletter(prev)
+                && (next == W::MidLetter || is_mid_num_let_q(next))
+                && next2_is(is_ahletter)
+            {
+                return false;
+            }
+
+            // WB7
+            if prev2_is(is_ahletter)
+                && (prev == W::MidLetter || is_mid_num_let_q(prev))
+                && is_ahletter(next)
+            {
+                return false;
+            }
+
+            // WB7a
+            if prev == W::HebrewLetter && next == W::SingleQuote {
+                return false;
+            }
+
+            // WB7b
+            if prev == W::HebrewLetter
+                && next == W::DoubleQuote
+                && next2 == Some(W::HebrewLetter)
+            {
+                return false;
+            }
+
+            // WB7c
+            if prev2 == Some(W::HebrewLetter)
+                && prev == W::DoubleQuote
+                && next == W::HebrewLetter
+            {
+                return false;
+            }
+
+            // WB8, WB9, WB10
+            if (prev == W::Numeric || is_ahletter(prev))
+                && (next == W::Numeric || is_ahletter(next))
+            {
+                return false;
+            }
+
+            // WB11
+            if prev2 == Some(W::Numeric)
+                && (prev == W::MidNum || is_mid_num_let_q(prev))
+                && next == W::Numeric
+            {
+                return false;
+            }
+
+            // WB12
+            if prev == W::Numeric
+                && (next == W::MidNum || is_mid_num_let_q(next))
+                && next2 == Some(W::Numeric)
+            {
+                return false;
+            }
+
+            // WB13
+            if prev == W::Katakana && next == W::Katakana {
+                return false;
+            }
+
+            // WB13a
+            if (is_ahletter(prev) || matches!(prev, W::Numeric | W::Katakana | W::ExtendNumLet))
+                && next == W::ExtendNumLet
+            {
+                return false;
+            }
+
+            // WB13b
+            if prev == W::ExtendNumLet
+                && (is_ahletter(next) || matches!(next, W::Numeric | W::Katakana))
+            {
+                return false;
+            }
+
+            // WB15, WB16
+            if prev == W::RegionalIndicator && next == W::RegionalIndicator {
+                let mut regional_indicators = 0;
+                let mut start = index;
+                while let Some((W::RegionalIndicator, prev_start)) = word_class_before(text, start) {
+                    regional_indicators += 1;
+                    start = prev_start;
+                }
+
+                if regional_indicators % 2 == 1 {
+                    return false;
+                }
+            }
+
+            // WB999
+            true
+        }
+
+        /// An iterator over the word boundaries in UTF-16 text, as defined by
+        /// UAX #29.  Each boundary is exposed as the index of the code unit
+        /// just past the end of a word (or of a run of text between words, such
+        /// as spaces or punctuation), so the last boundary is the length of the
+        /// text.  (The boundary at index 0 is not exposed.)
+        ///
+        /// ```text
+        /// // "can't stop"
+        /// let text: Vec<u16> = "can't stop".encode_utf16().collect();
+        /// let boundaries: Vec<usize> = WordBoundaries::new(&text).collect();
+        /// assert_eq!(boundaries, vec![5, 6, 10]);
+        /// ```
+        pub struct WordBoundaries<'a> {
+            text: &'a [u16],
+            position: usize,
+        }
+
+        impl<'a> WordBoundaries<'a> {
+            pub fn new(text: &'a [u16]) -> WordBoundaries<'a> {
+                WordBoundaries { text, position: 0 }
+            }
+        }
+
+        impl<'a> Iterator for WordBoundaries<'a> {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                let boundary = next_segmentation_boundary(self.text, self.position, is_word_boundary)?;
+                self.position = boundary;
+                Some(boundary)
+            }
+        }
+
+        /// An iterator over the word boundaries in UTF-8 text, exposed as byte
+        /// indexes, exactly as `WordBoundaries` does for UTF-16 text.
+        pub struct Utf8WordBoundaries<'a> {
+            text: &'a str,
+            position: usize,
+        }
+
+        impl<'a> Utf8WordBoundaries<'a> {
+            pub fn new(text: &'a str) -> Utf8WordBoundaries<'a> {
+                Utf8WordBoundaries { text, position: 0 }
+            }
+        }
+
+        impl<'a> Iterator for Utf8WordBoundaries<'a> {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                let boundary = next_segmentation_boundary(self.text, self.position, is_word_boundary)?;
+                self.position = boundary;
+                Some(boundary)
+            }
+        }
+    }
+}
+
+/// Generate the Word_Break property and word segmentation iterators.
+pub fn generate_word_break_code() -> proc_macro2::TokenStream {
+    let function = generate_word_break_function();
+    let iterators = generate_word_boundary_iterators();
+
+    quote! {
+        #function
+
+        #iterators
+    }
+}
@@ -25,68 +25,102 @@ fn check_folding_tables() {
     assert_eq!(folding_index2.len(), 1856);
 }
 
+#[test]
+fn check_char_info_and_fold_delta() {
+    const LATIN_CAPITAL_LETTER_A_WITH_GRAVE: u16 = 0xC0;
+
+    let cinfo = char_info('A' as u16);
+    assert_eq!(cinfo.upper_delta.0, 0);
+    assert_eq!(cinfo.lower_delta.0, 32);
+    assert!(cinfo.flags.is_unicode_id_start());
+
+    let cinfo = char_info('a' as u16);
+    assert_eq!(
+        u16::wrapping_add('a' as u16, cinfo.upper_delta.0),
+        'A' as u16
+    );
+    assert_eq!(cinfo.lower_delta.0, 0);
+
+    assert!(!char_info('1' as u16).flags.is_unicode_id_start());
+    assert!(char_info('1' as u16).flags.is_unicode_id_continue_only());
+    assert!(char_info(' ' as u16).flags.is_space());
+
+    assert_eq!(fold_delta('A' as u16).0, 32);
+    assert_eq!(fold_delta(LATIN_CAPITAL_LETTER_A_WITH_GRAVE).0, 32);
+    assert_eq!(fold_delta('a' as u16).0, 0);
+    assert_eq!(fold_delta('1' as u16).0, 0);
+}
+
 #[test]
 fn check_isidentifier_start_non_bmp() {
     // This code point constitutes a full range.
     const TIRHUTA_OM: u32 = 0x114C7;
-    assert_eq!(is_identifier_start_non_bmp(TIRHUTA_OM), true);
+    assert!(is_identifier_start_non_bmp(TIRHUTA_OM));
 
-    assert_eq!(is_identifier_start_non_bmp(TIRHUTA_OM - 1), false);
-    assert_eq!(is_identifier_start_non_bmp(TIRHUTA_OM + 1), false);
+    assert!(!is_identifier_start_non_bmp(TIRHUTA_OM - 1));
+    assert!(!is_identifier_start_non_bmp(TIRHUTA_OM + 1));
 }
 
 #[test]
 fn check_isidentifier_part_non_bmp() {
     // 0x11370..=0x11374 is a full range.
     const COMBINING_GRANTHA_LETTER_A: u32 = 0x11370;
-    assert_eq!(
-        is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A - 1),
-        false
-    );
-    assert_eq!(
-        is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 0),
-        true
-    );
-    assert_eq!(
-        is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 1),
-        true
-    );
-    assert_eq!(
-        is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 2),
-        true
-    );
-    assert_eq!(
-        is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 3),
-        true
-    );
-    assert_eq!(
-        is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 4),
-        true
-    );
-    assert_eq!(
-        is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 5),
-        false
-    );
+    assert!(!is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A - 1));
+    assert!(is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A));
+    assert!(is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 1));
+    assert!(is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 2));
+    assert!(is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 3));
+    assert!(is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 4));
+    assert!(!is_identifier_part_non_bmp(COMBINING_GRANTHA_LETTER_A + 5));
+}
+
+#[test]
+fn check_is_identifier_start_part() {
+    const LATIN_SMALL_LETTER_E_WITH_ACUTE: u32 = 0xE9;
+    const COMBINING_GRAVE_ACCENT: u32 = 0x300;
+    const ZWNJ: u32 = 0x200C;
+    const ZWJ: u32 = 0x200D;
+    const DESERET_CAPITAL_LETTER_LONG_I: u32 = 0x10400;
+    const COMBINING_GRANTHA_LETTER_A: u32 = 0x11370;
+
+    for &code in &['$', '_', 'a', 'Z'] {
+        assert!(is_identifier_start(code as u32));
+        assert!(is_identifier_part(code as u32));
+    }
+    assert!(!is_identifier_start('7' as u32));
+    assert!(is_identifier_part('7' as u32));
+    assert!(!is_identifier_start(' ' as u32));
+    assert!(!is_identifier_part(' ' as u32));
+
+    assert!(is_identifier_start(LATIN_SMALL_LETTER_E_WITH_ACUTE));
+    assert!(is_identifier_part(LATIN_SMALL_LETTER_E_WITH_ACUTE));
+    assert!(!is_identifier_start(COMBINING_GRAVE_ACCENT));
+    assert!(is_identifier_part(COMBINING_GRAVE_ACCENT));
+
+    for &code in &[ZWNJ, ZWJ] {
+        assert!(!is_identifier_start(code));
+        assert!(is_identifier_part(code));
+    }
+
+    assert!(is_identifier_start(DESERET_CAPITAL_LETTER_LONG_I));
+    assert!(is_identifier_part(DESERET_CAPITAL_LETTER_LONG_I));
+    assert!(!is_identifier_start(COMBINING_GRANTHA_LETTER_A));
+    assert!(is_identifier_part(COMBINING_GRANTHA_LETTER_A));
 }
 
 #[test]
 fn check_changes_when_upper_cased_special_casing() {
-    assert_eq!(changes_when_upper_cased_special_casing('a' as u16), false);
+    assert!(!changes_when_upper_cased_special_casing('a' as u16));
 
-    assert_eq!(
-        changes_when_upper_cased_special_casing(LATIN_SMALL_LETTER_SHARP_S as u16),
-        true
-    );
-    assert_eq!(
-        changes_when_upper_cased_special_casing(
-            GREEK_SMALL_LETTER_IOTA_WITH_DIALYTIKA_AND_TONOS as u16
-        ),
-        true
-    );
-    assert_eq!(
-        changes_when_upper_cased_special_casing(GREEK_SMALL_LETTER_UPSILON_WITH_PSILI as u16),
-        true
-    );
+    assert!(changes_when_upper_cased_special_casing(
+        LATIN_SMALL_LETTER_SHARP_S as u16
+    ));
+    assert!(changes_when_upper_cased_special_casing(
+        GREEK_SMALL_LETTER_IOTA_WITH_DIALYTIKA_AND_TONOS as u16
+    ));
+    assert!(changes_when_upper_cased_special_casing(
+        GREEK_SMALL_LETTER_UPSILON_WITH_PSILI as u16
+    ));
 }
 
 #[test]
@@ -178,7 +212,7 @@ fn check_latin1_lookup_tables() {
                 .to_string()
                 .to_lowercase()
                 .chars()
-                .nth(0)
+                .next()
                 .expect("lowercasing can't empty"),
             *c as char
         );
@@ -304,3 +338,1512 @@ fn check_to_lower_case_non_bmp_trail() {
     */
     assert!(to_lower_case_non_bmp_trail(0xD801, 0xDC00) == 0xDC28);
 }
+
+#[test]
+fn check_to_upper_lower_case() {
+    const LATIN_SMALL_LETTER_E_WITH_ACUTE: u32 = 0xE9;
+    const LATIN_CAPITAL_LETTER_E_WITH_ACUTE: u32 = 0xC9;
+    const DESERET_CAPITAL_LETTER_LONG_I: u32 = 0x10400;
+    const DESERET_SMALL_LETTER_LONG_I: u32 = 0x10428;
+
+    assert_eq!(to_upper_case('a' as u32), 'A' as u32);
+    assert_eq!(to_upper_case('A' as u32), 'A' as u32);
+    assert_eq!(to_lower_case('Z' as u32), 'z' as u32);
+    assert_eq!(to_lower_case('1' as u32), '1' as u32);
+
+    assert_eq!(
+        to_upper_case(LATIN_SMALL_LETTER_E_WITH_ACUTE),
+        LATIN_CAPITAL_LETTER_E_WITH_ACUTE
+    );
+    assert_eq!(
+        to_lower_case(LATIN_CAPITAL_LETTER_E_WITH_ACUTE),
+        LATIN_SMALL_LETTER_E_WITH_ACUTE
+    );
+    // Only full case mapping uppercases this to "SS".
+    assert_eq!(
+        to_upper_case(LATIN_SMALL_LETTER_SHARP_S),
+        LATIN_SMALL_LETTER_SHARP_S
+    );
+
+    assert_eq!(
+        to_upper_case(DESERET_SMALL_LETTER_LONG_I),
+        DESERET_CAPITAL_LETTER_LONG_I
+    );
+    assert_eq!(
+        to_lower_case(DESERET_CAPITAL_LETTER_LONG_I),
+        DESERET_SMALL_LETTER_LONG_I
+    );
+    assert_eq!(
+        to_lower_case(DESERET_SMALL_LETTER_LONG_I),
+        DESERET_SMALL_LETTER_LONG_I
+    );
+    assert_eq!(to_upper_case(0x10FFFF), 0x10FFFF);
+
+    // Values beyond 0x10FFFF aren't code points, and are returned unchanged.
+    assert_eq!(to_upper_case(0x110000), 0x110000);
+    assert_eq!(to_upper_case(u32::MAX), u32::MAX);
+    assert_eq!(to_lower_case(u32::MAX), u32::MAX);
+}
+
+#[test]
+fn check_to_upper_case_utf16() {
+    let upper = |text: &[u16]| {
+        let mut output = vec![0x2A];
+        to_upper_case_utf16(text, &mut output);
+        output
+    };
+
+    assert_eq!(upper(&[]), [0x2A]);
+    // "aß1" -> "ASS1"
+    assert_eq!(upper(&[0x61, 0xDF, 0x31]), [0x2A, 0x41, 0x53, 0x53, 0x31]);
+    // "Straße" -> "STRASSE"
+    assert_eq!(
+        upper(&[0x53, 0x74, 0x72, 0x61, 0xDF, 0x65]),
+        [0x2A, 0x53, 0x54, 0x52, 0x41, 0x53, 0x53, 0x45]
+    );
+    // U+0390 GREEK SMALL LETTER IOTA WITH DIALYTIKA AND TONOS
+    assert_eq!(
+        upper(&[GREEK_SMALL_LETTER_IOTA_WITH_DIALYTIKA_AND_TONOS as u16]),
+        [0x2A, GREEK_CAPITAL_LETTER_IOTA as u16, 0x308, 0x301]
+    );
+    // U+10428 DESERET SMALL LETTER LONG I -> U+10400
+    assert_eq!(upper(&[0xD801, 0xDC28, 0x62]), [0x2A, 0xD801, 0xDC00, 0x42]);
+    // Unpaired surrogates are copied.
+    assert_eq!(upper(&[0xDC28, 0xD801]), [0x2A, 0xDC28, 0xD801]);
+}
+
+#[test]
+fn check_to_lower_case_utf16() {
+    const GREEK_CAPITAL_LETTER_SIGMA: u16 = 0x3A3;
+    const GREEK_SMALL_LETTER_SIGMA: u16 = 0x3C3;
+    const GREEK_SMALL_LETTER_FINAL_SIGMA: u16 = 0x3C2;
+
+    let lower = |text: &[u16]| {
+        let mut output = vec![];
+        to_lower_case_utf16(text, &mut output);
+        output
+    };
+
+    assert_eq!(lower(&[0x41, 0x62, 0x31]), [0x61, 0x62, 0x31]);
+    // U+0130 LATIN CAPITAL LETTER I WITH DOT ABOVE
+    assert_eq!(lower(&[0x130]), [0x69, 0x307]);
+    // U+10400 DESERET CAPITAL LETTER LONG I -> U+10428
+    assert_eq!(lower(&[0xD801, 0xDC00]), [0xD801, 0xDC28]);
+    assert_eq!(lower(&[0xD801]), [0xD801]);
+
+    // A sigma is final if preceded by a cased letter and not followed by one,
+    // ignoring case-ignorable code points such as "'" and "." between them.
+    assert_eq!(
+        lower(&[0x41, GREEK_CAPITAL_LETTER_SIGMA]),
+        [0x61, GREEK_SMALL_LETTER_FINAL_SIGMA]
+    );
+    // "ΟΔΟΣ" -> "οδος"
+    assert_eq!(
+        lower(&[0x39F, 0x394, 0x39F, GREEK_CAPITAL_LETTER_SIGMA]),
+        [0x3BF, 0x3B4, 0x3BF, GREEK_SMALL_LETTER_FINAL_SIGMA]
+    );
+    assert_eq!(
+        lower(&[0x41, 0x27, GREEK_CAPITAL_LETTER_SIGMA, 0x2E, 0x20, 0x42]),
+        [0x61, 0x27, GREEK_SMALL_LETTER_FINAL_SIGMA, 0x2E, 0x20, 0x62]
+    );
+    assert_eq!(
+        lower(&[0x41, GREEK_CAPITAL_LETTER_SIGMA, 0x2E, 0x42]),
+        [0x61, GREEK_SMALL_LETTER_SIGMA, 0x2E, 0x62]
+    );
+    assert_eq!(
+        lower(&[GREEK_CAPITAL_LETTER_SIGMA]),
+        [GREEK_SMALL_LETTER_SIGMA]
+    );
+    assert_eq!(
+        lower(&[0x31, GREEK_CAPITAL_LETTER_SIGMA]),
+        [0x31, GREEK_SMALL_LETTER_SIGMA]
+    );
+    // U+10400 is cased.
+    assert_eq!(
+        lower(&[0xD801, 0xDC00, GREEK_CAPITAL_LETTER_SIGMA]),
+        [0xD801, 0xDC28, GREEK_SMALL_LETTER_FINAL_SIGMA]
+    );
+}
+
+#[test]
+fn check_canonical_combining_class() {
+    assert_eq!(canonical_combining_class('a' as u32), 0);
+    assert_eq!(canonical_combining_class(LATIN_SMALL_LETTER_SHARP_S), 0);
+    assert_eq!(canonical_combining_class(COMBINING_ACUTE_ACCENT), 230);
+    assert_eq!(canonical_combining_class(COMBINING_MACRON_BELOW), 220);
+
+    // U+05B0 HEBREW POINT SHEVA
+    assert_eq!(canonical_combining_class(0x05B0), 10);
+    // U+0327 COMBINING CEDILLA
+    assert_eq!(canonical_combining_class(0x0327), 202);
+    // U+1D165 MUSICAL SYMBOL COMBINING STEM
+    assert_eq!(canonical_combining_class(0x1D165), 216);
+
+    // Unassigned and the last code point.
+    assert_eq!(canonical_combining_class(0x0378), 0);
+    assert_eq!(canonical_combining_class(0x10FFFF), 0);
+}
+
+#[test]
+fn check_code_point_lookup_out_of_range() {
+    // Values that aren't code points get the value of an unassigned code point
+    // rather than indexing outside the tables.
+    for &code in &[0x110000, 0x7FFF_FFFF, u32::MAX] {
+        assert_eq!(canonical_combining_class(code), 0);
+        assert_eq!(nfc_quick_check(code), QuickCheck::Yes);
+        assert_eq!(bidi_class(code), BidiClass::L);
+        assert_eq!(identifier_types(code), IDENTIFIER_TYPE_NOT_CHARACTER);
+        assert_eq!(nfkc_casefold(code), None);
+    }
+}
+
+#[test]
+fn check_normalization_quick_check() {
+    assert_eq!(nfc_quick_check('a' as u32), QuickCheck::Yes);
+    assert_eq!(nfd_quick_check('a' as u32), QuickCheck::Yes);
+    assert_eq!(nfkc_quick_check('a' as u32), QuickCheck::Yes);
+    assert_eq!(nfkd_quick_check('a' as u32), QuickCheck::Yes);
+
+    // COMBINING ACUTE ACCENT may compose with a preceding base.
+    assert_eq!(nfc_quick_check(COMBINING_ACUTE_ACCENT), QuickCheck::Maybe);
+    assert_eq!(nfd_quick_check(COMBINING_ACUTE_ACCENT), QuickCheck::Yes);
+
+    // U+00C0 LATIN CAPITAL LETTER A WITH GRAVE
+    assert_eq!(nfc_quick_check(0xC0), QuickCheck::Yes);
+    assert_eq!(nfd_quick_check(0xC0), QuickCheck::No);
+    assert_eq!(nfkd_quick_check(0xC0), QuickCheck::No);
+
+    // U+00A0 NO-BREAK SPACE
+    assert_eq!(nfd_quick_check(0xA0), QuickCheck::Yes);
+    assert_eq!(nfkc_quick_check(0xA0), QuickCheck::No);
+
+    // U+AC00 HANGUL SYLLABLE GA
+    assert_eq!(nfd_quick_check(0xAC00), QuickCheck::No);
+    // U+1D15E MUSICAL SYMBOL HALF NOTE
+    assert_eq!(nfc_quick_check(0x1D15E), QuickCheck::No);
+}
+
+#[test]
+fn check_latin1_quick_check_table() {
+    assert_eq!(latin1_quick_check_table['a' as usize], 0);
+    assert_eq!(
+        latin1_quick_check_table[0xA0],
+        QUICK_CHECK_NFKC | QUICK_CHECK_NFKD
+    );
+    assert_eq!(
+        latin1_quick_check_table[0xC0],
+        QUICK_CHECK_NFD | QUICK_CHECK_NFKD
+    );
+
+    for code in 0..=0xFFu32 {
+        assert_eq!(latin1_quick_check_table[code as usize] & QUICK_CHECK_NFC, 0);
+    }
+}
+
+#[test]
+fn check_grapheme_cluster_break() {
+    assert_eq!(
+        grapheme_cluster_break('a' as u32),
+        GraphemeClusterBreak::Other
+    );
+    assert_eq!(
+        grapheme_cluster_break('\r' as u32),
+        GraphemeClusterBreak::Cr
+    );
+    assert_eq!(
+        grapheme_cluster_break(COMBINING_ACUTE_ACCENT),
+        GraphemeClusterBreak::Extend
+    );
+    // U+1F1FA REGIONAL INDICATOR SYMBOL LETTER U
+    assert_eq!(
+        grapheme_cluster_break(0x1F1FA),
+        GraphemeClusterBreak::RegionalIndicator
+    );
+    // U+AC00 HANGUL SYLLABLE GA
+    assert_eq!(grapheme_cluster_break(0xAC00), GraphemeClusterBreak::Lv);
+
+    // U+1F600 GRINNING FACE
+    assert!(is_extended_pictographic(0x1F600));
+    assert!(!is_extended_pictographic('a' as u32));
+
+    // U+0915 DEVANAGARI LETTER KA, U+094D DEVANAGARI SIGN VIRAMA
+    assert_eq!(indic_conjunct_break(0x0915), IndicConjunctBreak::Consonant);
+    assert_eq!(indic_conjunct_break(0x094D), IndicConjunctBreak::Linker);
+    assert_eq!(indic_conjunct_break('a' as u32), IndicConjunctBreak::None);
+}
+
+#[test]
+fn check_grapheme_cluster_boundaries() {
+    fn boundaries(text: &[u16]) -> Vec<usize> {
+        GraphemeClusterBoundaries::new(text).collect()
+    }
+
+    assert_eq!(boundaries(&[]), vec![]);
+    assert_eq!(boundaries(&[0x61, 0x62]), vec![1, 2]);
+
+    // GB3, GB4, GB5
+    assert_eq!(boundaries(&[0x0D, 0x0A, 0x61, 0x0A]), vec![2, 3, 4]);
+
+    // GB9: "e" followed by COMBINING ACUTE ACCENT
+    assert_eq!(boundaries(&[0x65, 0x0301, 0x61]), vec![2, 3]);
+
+    // GB6, GB8: HANGUL CHOSEONG KIYEOK, HANGUL JUNGSEONG A, HANGUL JONGSEONG
+    // KIYEOK
+    assert_eq!(boundaries(&[0x1100, 0x1161, 0x11A8, 0x61]), vec![3, 4]);
+
+    // GB9c: DEVANAGARI LETTER KA, SIGN VIRAMA, LETTER SSA
+    assert_eq!(boundaries(&[0x0915, 0x094D, 0x0937]), vec![3]);
+
+    // GB11: MAN, ZERO WIDTH JOINER, WOMAN
+    assert_eq!(
+        boundaries(&[0xD83D, 0xDC68, 0x200D, 0xD83D, 0xDC69]),
+        vec![5]
+    );
+
+    // GB12, GB13: three regional indicators
+    assert_eq!(
+        boundaries(&[0xD83C, 0xDDFA, 0xD83C, 0xDDF8, 0xD83C, 0xDDFA]),
+        vec![4, 6]
+    );
+
+    // Unpaired surrogates are treated as Control.
+    assert_eq!(boundaries(&[0xD800, 0x0301]), vec![1, 2]);
+}
+
+#[test]
+fn check_word_boundaries() {
+    fn boundaries(text: &str) -> Vec<usize> {
+        let utf16: Vec<u16> = text.encode_utf16().collect();
+        WordBoundaries::new(&utf16).collect()
+    }
+
+    fn utf8_boundaries(text: &str) -> Vec<usize> {
+        Utf8WordBoundaries::new(text).collect()
+    }
+
+    assert_eq!(word_break('a' as u32), WordBreak::ALetter);
+    assert_eq!(word_break('\'' as u32), WordBreak::SingleQuote);
+    assert_eq!(word_break(' ' as u32), WordBreak::WSegSpace);
+
+    assert_eq!(boundaries(""), vec![]);
+
+    // WB3d, WB5, WB6, WB7
+    assert_eq!(boundaries("can't  stop"), vec![5, 7, 11]);
+    assert_eq!(utf8_boundaries("can't  stop"), vec![5, 7, 11]);
+
+    // WB8, WB11, WB12
+    assert_eq!(boundaries("3.14,15 x"), vec![7, 8, 9]);
+
+    // WB4: COMBINING ACUTE ACCENT doesn't interrupt a word.
+    assert_eq!(boundaries("e\u{301}te."), vec![4, 5]);
+    assert_eq!(utf8_boundaries("e\u{301}te."), vec![5, 6]);
+
+    // WB13a, WB13b
+    assert_eq!(boundaries("snake_case_2 x"), vec![12, 13, 14]);
+
+    // WB3, WB3a
+    assert_eq!(boundaries("a\r\nb"), vec![1, 3, 4]);
+
+    // WB15, WB16: three regional indicators
+    assert_eq!(boundaries("\u{1F1FA}\u{1F1F8}\u{1F1FA}"), vec![4, 6]);
+    assert_eq!(utf8_boundaries("\u{1F1FA}\u{1F1F8}\u{1F1FA}"), vec![8, 12]);
+}
+
+#[test]
+fn check_sentence_boundaries() {
+    fn boundaries(text: &str) -> Vec<usize> {
+        let utf16: Vec<u16> = text.encode_utf16().collect();
+        SentenceBoundaries::new(&utf16).collect()
+    }
+
+    fn utf8_boundaries(text: &str) -> Vec<usize> {
+        Utf8SentenceBoundaries::new(text).collect()
+    }
+
+    assert_eq!(sentence_break('a' as u32), SentenceBreak::Lower);
+    assert_eq!(sentence_break('.' as u32), SentenceBreak::ATerm);
+    assert_eq!(sentence_break('?' as u32), SentenceBreak::STerm);
+
+    assert_eq!(boundaries(""), vec![]);
+
+    // SB9, SB10, SB11
+    assert_eq!(boundaries("Hi there. Bye."), vec![10, 14]);
+    assert_eq!(boundaries("Really?\" Yes."), vec![9, 13]);
+
+    // SB6: no boundary within a decimal number
+    assert_eq!(boundaries("Pi is 3.14 or so."), vec![17]);
+
+    // SB7: no boundary within an abbreviation followed by an uppercase letter
+    assert_eq!(boundaries("See U.S.A."), vec![10]);
+
+    // SB8: no boundary before a lowercase word
+    assert_eq!(boundaries("Apples, etc. are fruit."), vec![23]);
+
+    // SB4: boundary after a paragraph separator
+    assert_eq!(boundaries("One\nTwo"), vec![4, 7]);
+    assert_eq!(utf8_boundaries("\u{e9}t\u{e9}. Oui."), vec![7, 11]);
+}
+
+#[test]
+fn check_line_break() {
+    assert_eq!(line_break('a' as u32), LineBreak::Al);
+    assert_eq!(line_break(' ' as u32), LineBreak::Sp);
+    assert_eq!(line_break('(' as u32), LineBreak::Op);
+    // U+4E00 CJK UNIFIED IDEOGRAPH-4E00
+    assert_eq!(line_break(0x4E00), LineBreak::Id);
+
+    assert_eq!(line_break_class('a' as u32), LineBreakClass::Al);
+    assert_eq!(line_break_class('(' as u32), LineBreakClass::Op);
+    // U+FF08 FULLWIDTH LEFT PARENTHESIS
+    assert_eq!(line_break(0xFF08), LineBreak::Op);
+    assert_eq!(line_break_class(0xFF08), LineBreakClass::OpEastAsian);
+    // U+0E01 THAI CHARACTER KO KAI, U+0E31 THAI CHARACTER MAI HAN-AKAT
+    assert_eq!(line_break(0x0E01), LineBreak::Sa);
+    assert_eq!(line_break_class(0x0E01), LineBreakClass::Al);
+    assert_eq!(line_break_class(0x0E31), LineBreakClass::Cm);
+    // U+00AB LEFT-POINTING DOUBLE ANGLE QUOTATION MARK (Pi), U+00BB
+    // RIGHT-POINTING DOUBLE ANGLE QUOTATION MARK (Pf)
+    assert_eq!(line_break_class('"' as u32), LineBreakClass::Qu);
+    assert_eq!(line_break(0xAB), LineBreak::Qu);
+    assert_eq!(line_break_class(0xAB), LineBreakClass::QuPi);
+    assert_eq!(line_break_class(0xBB), LineBreakClass::QuPf);
+}
+
+#[test]
+fn check_line_break_opportunities() {
+    fn opportunities(text: &str) -> Vec<(usize, bool)> {
+        let utf16: Vec<u16> = text.encode_utf16().collect();
+        LineBreakOpportunities::new(&utf16)
+            .map(|opportunity| (opportunity.index, opportunity.mandatory))
+            .collect()
+    }
+
+    assert_eq!(opportunities(""), vec![]);
+
+    // LB3, LB4, LB5, LB18
+    assert_eq!(
+        opportunities("a b\nc"),
+        vec![(2, false), (4, true), (5, true)]
+    );
+    assert_eq!(opportunities("a\r\nb"), vec![(3, true), (4, true)]);
+    assert_eq!(opportunities("a  b"), vec![(3, false), (4, true)]);
+
+    // LB9: combining marks attach to the preceding character.
+    assert_eq!(opportunities("e\u{301} b"), vec![(3, false), (4, true)]);
+
+    // LB13, LB14
+    assert_eq!(opportunities("(a) b"), vec![(4, false), (5, true)]);
+    assert_eq!(opportunities("( a )"), vec![(5, true)]);
+
+    // LB15a: no break after an initial quotation mark, and any spaces, that
+    // opens a quotation.
+    assert_eq!(opportunities("x \u{AB} a"), vec![(2, false), (5, true)]);
+    assert_eq!(opportunities("x\u{AB} a"), vec![(3, false), (4, true)]);
+    assert_eq!(opportunities("(\u{AB} a"), vec![(4, true)]);
+
+    // LB15b: no break before a final quotation mark that closes a quotation.
+    assert_eq!(opportunities("a \u{BB} b"), vec![(4, false), (5, true)]);
+    assert_eq!(opportunities("a \u{BB}"), vec![(3, true)]);
+    assert_eq!(opportunities("a \u{BB}b"), vec![(2, false), (4, true)]);
+
+    // LB15c, LB15d: breaks are permitted between spaces and a number
+    // beginning with IS, but not before IS otherwise.
+    assert_eq!(opportunities("a .5"), vec![(2, false), (4, true)]);
+    assert_eq!(opportunities("a .b"), vec![(4, true)]);
+    assert_eq!(opportunities("( .5"), vec![(4, true)]);
+
+    // LB21a: no break after a hyphen or U+05BE HEBREW PUNCTUATION MAQAF
+    // following a Hebrew letter.
+    assert_eq!(opportunities("a-b"), vec![(2, false), (3, true)]);
+    assert_eq!(opportunities("\u{5D0}-\u{5D1}"), vec![(3, true)]);
+    assert_eq!(opportunities("\u{5D0}\u{5BE}\u{5D1}"), vec![(3, true)]);
+
+    // LB25
+    assert_eq!(opportunities("$100"), vec![(4, true)]);
+
+    // LB30: no break between a letter and non-East-Asian parenthesis, but
+    // breaks are permitted before fullwidth parentheses.
+    assert_eq!(opportunities("a(b"), vec![(3, true)]);
+    assert_eq!(opportunities("a\u{FF08}b"), vec![(1, false), (3, true)]);
+
+    // LB31: ideographs may be broken between.
+    assert_eq!(
+        opportunities("\u{4E00}\u{4E8C}"),
+        vec![(1, false), (2, true)]
+    );
+
+    // LB30a: three regional indicators
+    assert_eq!(
+        opportunities("\u{1F1FA}\u{1F1F8}\u{1F1FA}"),
+        vec![(4, false), (6, true)]
+    );
+}
+
+#[test]
+fn check_east_asian_width() {
+    assert_eq!(east_asian_width('a' as u32), EastAsianWidth::Narrow);
+    assert_eq!(east_asian_width(0xA1), EastAsianWidth::Ambiguous);
+    // U+3042 HIRAGANA LETTER A
+    assert_eq!(east_asian_width(0x3042), EastAsianWidth::Wide);
+    // U+FF21 FULLWIDTH LATIN CAPITAL LETTER A
+    assert_eq!(east_asian_width(0xFF21), EastAsianWidth::Fullwidth);
+    // U+FF71 HALFWIDTH KATAKANA LETTER A
+    assert_eq!(east_asian_width(0xFF71), EastAsianWidth::Halfwidth);
+    // U+0100 LATIN CAPITAL LETTER A WITH MACRON
+    assert_eq!(east_asian_width(0x0100), EastAsianWidth::Neutral);
+    // U+3400 CJK UNIFIED IDEOGRAPH-3400, unlisted but defaulting to Wide
+    assert_eq!(east_asian_width(0x3400), EastAsianWidth::Wide);
+}
+
+#[test]
+fn check_display_width() {
+    assert_eq!(display_width('a' as u32), 1);
+    assert_eq!(display_width(0xA1), 1);
+    assert_eq!(display_width(0x3042), 2);
+    assert_eq!(display_width(0xFF21), 2);
+    assert_eq!(display_width(0xFF71), 1);
+    // U+1F600 GRINNING FACE
+    assert_eq!(display_width(0x1F600), 2);
+
+    // Controls
+    assert_eq!(display_width('\n' as u32), 0);
+    assert_eq!(display_width(0x7F), 0);
+    // U+0301 COMBINING ACUTE ACCENT, U+20DD COMBINING ENCLOSING CIRCLE
+    assert_eq!(display_width(0x0301), 0);
+    assert_eq!(display_width(0x20DD), 0);
+    // U+00AD SOFT HYPHEN, U+200B ZERO WIDTH SPACE, U+FE0F VARIATION SELECTOR-16
+    assert_eq!(display_width(0xAD), 0);
+    assert_eq!(display_width(0x200B), 0);
+    assert_eq!(display_width(0xFE0F), 0);
+}
+
+#[test]
+fn check_bidi_class() {
+    assert_eq!(bidi_class('a' as u32), BidiClass::L);
+    assert_eq!(bidi_class('1' as u32), BidiClass::En);
+    assert_eq!(bidi_class(' ' as u32), BidiClass::Ws);
+    assert_eq!(bidi_class('\n' as u32), BidiClass::B);
+    // U+05D0 HEBREW LETTER ALEF, U+0627 ARABIC LETTER ALEF
+    assert_eq!(bidi_class(0x05D0), BidiClass::R);
+    assert_eq!(bidi_class(0x0627), BidiClass::Al);
+    // U+0301 COMBINING ACUTE ACCENT, U+2067 RIGHT-TO-LEFT ISOLATE
+    assert_eq!(bidi_class(0x0301), BidiClass::Nsm);
+    assert_eq!(bidi_class(0x2067), BidiClass::Rli);
+
+    // Unassigned code points take their range's default.
+    assert_eq!(bidi_class(0x05FF), BidiClass::R);
+    assert_eq!(bidi_class(0x07BB), BidiClass::Al);
+    assert_eq!(bidi_class(0x20CF), BidiClass::Et);
+    assert_eq!(bidi_class(0xFDD0), BidiClass::Bn);
+    assert_eq!(bidi_class(0x50000), BidiClass::L);
+}
+
+#[test]
+fn check_bidi_mirroring() {
+    assert!(is_bidi_mirrored('(' as u32));
+    assert!(is_bidi_mirrored(0x2208));
+    assert!(!is_bidi_mirrored('a' as u32));
+
+    assert_eq!(bidi_mirroring_glyph('(' as u32), Some(')' as u32));
+    assert_eq!(bidi_mirroring_glyph('>' as u32), Some('<' as u32));
+    // U+00AB LEFT-POINTING DOUBLE ANGLE QUOTATION MARK
+    assert_eq!(bidi_mirroring_glyph(0xAB), Some(0xBB));
+    assert_eq!(bidi_mirroring_glyph('a' as u32), None);
+}
+
+#[test]
+fn check_bidi_paired_brackets() {
+    assert_eq!(
+        bidi_paired_bracket_type('[' as u32),
+        BidiPairedBracketType::Open
+    );
+    assert_eq!(
+        bidi_paired_bracket_type(']' as u32),
+        BidiPairedBracketType::Close
+    );
+    assert_eq!(
+        bidi_paired_bracket_type('<' as u32),
+        BidiPairedBracketType::None
+    );
+
+    assert_eq!(bidi_paired_bracket('[' as u32), Some(']' as u32));
+    assert_eq!(bidi_paired_bracket('}' as u32), Some('{' as u32));
+    // U+2329 LEFT-POINTING ANGLE BRACKET
+    assert_eq!(bidi_paired_bracket(0x2329), Some(0x232A));
+    assert_eq!(bidi_paired_bracket('<' as u32), None);
+}
+
+#[test]
+fn check_numeric_value() {
+    let value = numeric_value('7' as u32);
+    assert_eq!(value.numeric_type, NumericType::Decimal);
+    assert_eq!((value.numerator, value.denominator), (7, 1));
+
+    // U+00B2 SUPERSCRIPT TWO
+    let value = numeric_value(0xB2);
+    assert_eq!(value.numeric_type, NumericType::Digit);
+    assert_eq!((value.numerator, value.denominator), (2, 1));
+
+    // U+00BE VULGAR FRACTION THREE QUARTERS
+    let value = numeric_value(0xBE);
+    assert_eq!(value.numeric_type, NumericType::Numeric);
+    assert_eq!((value.numerator, value.denominator), (3, 4));
+
+    // U+216F ROMAN NUMERAL ONE THOUSAND
+    let value = numeric_value(0x216F);
+    assert_eq!(value.numeric_type, NumericType::Numeric);
+    assert_eq!((value.numerator, value.denominator), (1000, 1));
+
+    // U+0F33 TIBETAN DIGIT HALF ZERO
+    let value = numeric_value(0x0F33);
+    assert_eq!((value.numerator, value.denominator), (-1, 2));
+
+    let value = numeric_value('a' as u32);
+    assert_eq!(value.numeric_type, NumericType::None);
+    assert_eq!((value.numerator, value.denominator), (0, 1));
+}
+
+#[test]
+fn check_decimal_digit_value() {
+    for (i, digit) in ('0'..='9').enumerate() {
+        assert_eq!(decimal_digit_value(digit as u32), Some(i as u8));
+    }
+
+    // U+0669 ARABIC-INDIC DIGIT NINE
+    assert_eq!(decimal_digit_value(0x0669), Some(9));
+    // U+FF15 FULLWIDTH DIGIT FIVE
+    assert_eq!(decimal_digit_value(0xFF15), Some(5));
+    // U+1D7CE MATHEMATICAL BOLD DIGIT ZERO
+    assert_eq!(decimal_digit_value(0x1D7CE), Some(0));
+
+    assert_eq!(decimal_digit_value('a' as u32), None);
+    // U+00B2 SUPERSCRIPT TWO, U+2160 ROMAN NUMERAL ONE
+    assert_eq!(decimal_digit_value(0xB2), None);
+    assert_eq!(decimal_digit_value(0x2160), None);
+}
+
+#[test]
+fn check_char_name() {
+    assert_eq!(
+        char_name('a' as u32).as_deref(),
+        Some("LATIN SMALL LETTER A")
+    );
+    assert_eq!(
+        char_name(0xDF).as_deref(),
+        Some("LATIN SMALL LETTER SHARP S")
+    );
+    assert_eq!(char_name(0x0301).as_deref(), Some("COMBINING ACUTE ACCENT"));
+    assert_eq!(char_name(0x1F600).as_deref(), Some("GRINNING FACE"));
+    assert_eq!(
+        char_name(0x0F0A).as_deref(),
+        Some("TIBETAN MARK BKA- SHOG YIG MGO")
+    );
+
+    // Algorithmic names
+    assert_eq!(
+        char_name(0x4E00).as_deref(),
+        Some("CJK UNIFIED IDEOGRAPH-4E00")
+    );
+    assert_eq!(
+        char_name(0x20000).as_deref(),
+        Some("CJK UNIFIED IDEOGRAPH-20000")
+    );
+    assert_eq!(
+        char_name(0x17000).as_deref(),
+        Some("TANGUT IDEOGRAPH-17000")
+    );
+    assert_eq!(char_name(0xAC00).as_deref(), Some("HANGUL SYLLABLE GA"));
+    assert_eq!(char_name(0xAC01).as_deref(), Some("HANGUL SYLLABLE GAG"));
+    // U+C544 HANGUL SYLLABLE A, whose leading jamo has an empty short name
+    assert_eq!(char_name(0xC544).as_deref(), Some("HANGUL SYLLABLE A"));
+    assert_eq!(char_name(0xD7A3).as_deref(), Some("HANGUL SYLLABLE HIH"));
+
+    // Code points without names
+    assert_eq!(char_name(0x0A), None);
+    assert_eq!(char_name(0xD800), None);
+    assert_eq!(char_name(0xE000), None);
+    assert_eq!(char_name(0x50000), None);
+}
+
+#[test]
+fn check_lookup_by_name() {
+    assert_eq!(lookup_by_name("LATIN SMALL LETTER A"), Some('a' as u32));
+    assert_eq!(lookup_by_name("LATIN SMALL LETTER SHARP S"), Some(0xDF));
+    assert_eq!(lookup_by_name("latin small letter sharp s"), Some(0xDF));
+    assert_eq!(lookup_by_name("GRINNING FACE"), Some(0x1F600));
+    assert_eq!(
+        lookup_by_name("TIBETAN MARK BKA- SHOG YIG MGO"),
+        Some(0x0F0A)
+    );
+
+    // Name aliases
+    assert_eq!(lookup_by_name("LINE FEED"), Some(0x0A));
+    assert_eq!(lookup_by_name("NUL"), Some(0x00));
+    assert_eq!(lookup_by_name("BYTE ORDER MARK"), Some(0xFEFF));
+    assert_eq!(lookup_by_name("LATIN CAPITAL LETTER GHA"), Some(0x01A2));
+    assert_eq!(lookup_by_name("LATIN CAPITAL LETTER OI"), Some(0x01A2));
+
+    // Algorithmic names
+    assert_eq!(lookup_by_name("CJK UNIFIED IDEOGRAPH-4E00"), Some(0x4E00));
+    assert_eq!(lookup_by_name("CJK UNIFIED IDEOGRAPH-20000"), Some(0x20000));
+    assert_eq!(lookup_by_name("TANGUT IDEOGRAPH-17000"), Some(0x17000));
+    assert_eq!(lookup_by_name("HANGUL SYLLABLE GA"), Some(0xAC00));
+    assert_eq!(lookup_by_name("HANGUL SYLLABLE GAG"), Some(0xAC01));
+    assert_eq!(lookup_by_name("HANGUL SYLLABLE A"), Some(0xC544));
+    assert_eq!(lookup_by_name("HANGUL SYLLABLE HIH"), Some(0xD7A3));
+
+    assert_eq!(lookup_by_name(""), None);
+    assert_eq!(lookup_by_name("NOT A NAME"), None);
+    assert_eq!(lookup_by_name("CJK UNIFIED IDEOGRAPH-0041"), None);
+    assert_eq!(lookup_by_name("CJK UNIFIED IDEOGRAPH-04E00"), None);
+    assert_eq!(lookup_by_name("HANGUL SYLLABLE GX"), None);
+
+    // Every name round-trips.
+    for code in (0..0x11_0000).step_by(7) {
+        if let Some(name) = char_name(code) {
+            assert_eq!(lookup_by_name(&name), Some(code), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn check_age() {
+    assert!(UNICODE_VERSION >= (15, 1, 0));
+
+    assert_eq!(age('a' as u32), Some((1, 1)));
+    assert_eq!(age(0xDF), Some((1, 1)));
+    // U+20AC EURO SIGN
+    assert_eq!(age(0x20AC), Some((2, 1)));
+    // U+9FBB CJK UNIFIED IDEOGRAPH-9FBB
+    assert_eq!(age(0x9FBB), Some((4, 1)));
+    // U+1F600 GRINNING FACE
+    assert_eq!(age(0x1F600), Some((6, 1)));
+    // U+1F6DC WIRELESS
+    assert_eq!(age(0x1F6DC), Some((15, 0)));
+    // U+2EBF0 CJK UNIFIED IDEOGRAPH-2EBF0
+    assert_eq!(age(0x2EBF0), Some((15, 1)));
+
+    // Unassigned code points
+    assert_eq!(age(0x0378), None);
+    assert_eq!(age(0x50000), None);
+}
+
+#[test]
+fn check_block() {
+    assert_eq!(block('a' as u32), Some("Basic Latin"));
+    assert_eq!(block(0x7F), Some("Basic Latin"));
+    assert_eq!(block(0x80), Some("Latin-1 Supplement"));
+    assert_eq!(block(0xDF), Some("Latin-1 Supplement"));
+    assert_eq!(block(0x4E00), Some("CJK Unified Ideographs"));
+    assert_eq!(block(0xAC00), Some("Hangul Syllables"));
+    assert_eq!(block(0x1F600), Some("Emoticons"));
+    assert_eq!(block(0x10FFFF), Some("Supplementary Private Use Area-B"));
+
+    // No_Block
+    assert_eq!(block(0x50000), None);
+}
+
+#[test]
+fn check_hangul_syllable_type() {
+    assert_eq!(
+        hangul_syllable_type('a' as u32),
+        HangulSyllableType::NotApplicable
+    );
+    assert_eq!(hangul_syllable_type(0x1100), HangulSyllableType::L);
+    assert_eq!(hangul_syllable_type(0x1161), HangulSyllableType::V);
+    assert_eq!(hangul_syllable_type(0x11A8), HangulSyllableType::T);
+    assert_eq!(hangul_syllable_type(0xAC00), HangulSyllableType::Lv);
+    assert_eq!(hangul_syllable_type(0xAC01), HangulSyllableType::Lvt);
+    assert_eq!(hangul_syllable_type(0xD7A3), HangulSyllableType::Lvt);
+    assert_eq!(
+        hangul_syllable_type(0xD7A4),
+        HangulSyllableType::NotApplicable
+    );
+}
+
+#[test]
+fn check_hangul_composition() {
+    // U+AC00 HANGUL SYLLABLE GA
+    assert_eq!(
+        decompose_hangul_syllable(0xAC00),
+        Some((0x1100, 0x1161, None))
+    );
+    // U+AC01 HANGUL SYLLABLE GAG
+    assert_eq!(
+        decompose_hangul_syllable(0xAC01),
+        Some((0x1100, 0x1161, Some(0x11A8)))
+    );
+    // U+D7A3 HANGUL SYLLABLE HIH
+    assert_eq!(
+        decompose_hangul_syllable(0xD7A3),
+        Some((0x1112, 0x1175, Some(0x11C2)))
+    );
+    assert_eq!(decompose_hangul_syllable(0xABFF), None);
+    assert_eq!(decompose_hangul_syllable(0xD7A4), None);
+    assert_eq!(decompose_hangul_syllable(0x1100), None);
+
+    assert_eq!(compose_hangul(0x1100, 0x1161), Some(0xAC00));
+    assert_eq!(compose_hangul(0xAC00, 0x11A8), Some(0xAC01));
+    assert_eq!(compose_hangul(0x1112, 0x1175), Some(0xD788));
+    assert_eq!(compose_hangul(0xD788, 0x11C2), Some(0xD7A3));
+    // An LVT syllable doesn't compose with a further trailing jamo.
+    assert_eq!(compose_hangul(0xAC01, 0x11A8), None);
+    // U+11A7 is TBase, not a trailing jamo.
+    assert_eq!(compose_hangul(0xAC00, 0x11A7), None);
+    // U+1113 HANGUL CHOSEONG NIEUN-KIYEOK is a leading jamo, but not one of
+    // those that compose into syllables.
+    assert_eq!(compose_hangul(0x1113, 0x1161), None);
+    assert_eq!(compose_hangul('a' as u32, 0x1161), None);
+
+    // Every syllable recomposes from its decomposition.
+    for syllable in HANGUL_SYLLABLE_BASE..HANGUL_SYLLABLE_BASE + HANGUL_SYLLABLE_COUNT {
+        let (leading, vowel, trailing) = decompose_hangul_syllable(syllable).unwrap();
+        let lv = compose_hangul(leading, vowel).unwrap();
+        let composed = match trailing {
+            Some(trailing) => compose_hangul(lv, trailing).unwrap(),
+            None => lv,
+        };
+        assert_eq!(composed, syllable);
+    }
+}
+
+#[test]
+fn check_joining_type() {
+    assert_eq!(joining_type('a' as u32), JoiningType::NonJoining);
+    // U+0627 ARABIC LETTER ALEF, U+0628 ARABIC LETTER BEH
+    assert_eq!(joining_type(0x0627), JoiningType::RightJoining);
+    assert_eq!(joining_type(0x0628), JoiningType::DualJoining);
+    // U+0640 ARABIC TATWEEL
+    assert_eq!(joining_type(0x0640), JoiningType::JoinCausing);
+    // U+A872 PHAGS-PA SUPERFIXED LETTER RA
+    assert_eq!(joining_type(0xA872), JoiningType::LeftJoining);
+    // Unlisted Mn, Me and Cf code points are Transparent.
+    // U+064B ARABIC FATHATAN, U+20DD COMBINING ENCLOSING CIRCLE, U+00AD SOFT HYPHEN
+    assert_eq!(joining_type(0x064B), JoiningType::Transparent);
+    assert_eq!(joining_type(0x20DD), JoiningType::Transparent);
+    assert_eq!(joining_type(0xAD), JoiningType::Transparent);
+    // ZWNJ is listed as Non_Joining, and ZWJ as Join_Causing.
+    assert_eq!(joining_type(0x200C), JoiningType::NonJoining);
+    assert_eq!(joining_type(0x200D), JoiningType::JoinCausing);
+}
+
+#[test]
+fn check_joining_group() {
+    assert_eq!(joining_group(0x0622), Some("ALEF"));
+    assert_eq!(joining_group(0x0627), Some("ALEF"));
+    assert_eq!(joining_group(0x0628), Some("BEH"));
+    assert_eq!(joining_group(0x062E), Some("HAH"));
+    assert_eq!(joining_group(0x0600), None);
+    assert_eq!(joining_group('a' as u32), None);
+}
+
+#[test]
+fn check_is_joiner_permitted() {
+    const ZWNJ: u16 = 0x200C;
+    const ZWJ: u16 = 0x200D;
+
+    // A1: U+0628 ARABIC LETTER BEH (D), U+0644 ARABIC LETTER LAM (D), U+0627
+    // ARABIC LETTER ALEF (R), U+064B ARABIC FATHATAN (T)
+    assert!(is_joiner_permitted(&[0x0628, ZWNJ, 0x0644], 1));
+    assert!(is_joiner_permitted(&[0x0628, ZWNJ, 0x0627], 1));
+    assert!(is_joiner_permitted(
+        &[0x0628, 0x064B, ZWNJ, 0x064B, 0x0627],
+        2
+    ));
+    assert!(!is_joiner_permitted(&[0x0627, ZWNJ, 0x0628], 1));
+    assert!(!is_joiner_permitted(&[ZWNJ, 0x0628], 0));
+    assert!(!is_joiner_permitted(&[0x0628, ZWNJ], 1));
+    assert!(!is_joiner_permitted(&[0x0628, ZWNJ, 'a' as u16], 1));
+
+    // A2: U+0915 DEVANAGARI LETTER KA, U+094D DEVANAGARI SIGN VIRAMA, U+0951
+    // DEVANAGARI STRESS SIGN UDATTA.  The virama must immediately precede the
+    // joiner.
+    assert!(is_joiner_permitted(&[0x0915, 0x094D, ZWNJ], 2));
+    assert!(!is_joiner_permitted(&[0x0915, 0x094D, 0x0951, ZWNJ], 3));
+    assert!(!is_joiner_permitted(&[0x0915, ZWNJ], 1));
+
+    // B: U+093C DEVANAGARI SIGN NUKTA
+    assert!(is_joiner_permitted(&[0x0915, 0x094D, ZWJ, 0x0915], 2));
+    assert!(!is_joiner_permitted(&[0x0915, 0x094D, 0x093C, ZWJ], 3));
+    assert!(!is_joiner_permitted(&[0x0915, ZWJ], 1));
+    assert!(!is_joiner_permitted(&[0x0628, ZWJ, 0x0644], 1));
+
+    assert!(!is_joiner_permitted(&['a' as u16], 0));
+}
+
+#[test]
+fn check_code_point_flags() {
+    assert_eq!(code_point_flags('a' as u32), 0);
+    assert_eq!(code_point_flags(0x4E00), 0);
+    assert_eq!(code_point_flags(0xAD), CODE_POINT_FLAG_DEFAULT_IGNORABLE);
+    assert_eq!(
+        code_point_flags(0xFFFF),
+        CODE_POINT_FLAG_NONCHARACTER | CODE_POINT_FLAG_UNASSIGNED
+    );
+
+    // U+00AD SOFT HYPHEN, U+200B ZERO WIDTH SPACE, U+E0001 LANGUAGE TAG
+    assert!(is_default_ignorable(0xAD));
+    assert!(is_default_ignorable(0x200B));
+    assert!(is_default_ignorable(0xE0001));
+    assert!(!is_default_ignorable('a' as u32));
+
+    assert!(is_noncharacter(0xFDD0));
+    assert!(is_noncharacter(0xFFFE));
+    assert!(is_noncharacter(0x1FFFF));
+    assert!(is_noncharacter(0x10FFFF));
+    assert!(!is_noncharacter(0xFFFD));
+
+    assert!(is_private_use(0xE000));
+    assert!(is_private_use(0xF8FF));
+    assert!(is_private_use(0xF0000));
+    assert!(is_private_use(0x10FFFD));
+    assert!(!is_private_use(0x10FFFE));
+
+    assert!(is_surrogate(0xD800));
+    assert!(is_surrogate(0xDFFF));
+    assert!(!is_surrogate(0xE000));
+
+    assert!(is_unassigned(0x0378));
+    assert!(is_unassigned(0x50000));
+    assert!(is_unassigned(0xFDD0));
+    assert!(!is_unassigned('a' as u32));
+    assert!(!is_unassigned(0xD800));
+    assert!(!is_unassigned(0xE000));
+}
+
+#[test]
+fn check_identifier_status() {
+    assert_eq!(identifier_status('a' as u32), IdentifierStatus::Allowed);
+    assert_eq!(identifier_status('_' as u32), IdentifierStatus::Allowed);
+    assert_eq!(identifier_status(0xDF), IdentifierStatus::Allowed);
+    assert_eq!(identifier_status(0x4E00), IdentifierStatus::Allowed);
+    assert_eq!(identifier_status(0x20000), IdentifierStatus::Allowed);
+    // U+0430 CYRILLIC SMALL LETTER A
+    assert_eq!(identifier_status(0x0430), IdentifierStatus::Allowed);
+
+    assert_eq!(identifier_status('!' as u32), IdentifierStatus::Restricted);
+    // U+017F LATIN SMALL LETTER LONG S, U+2460 CIRCLED DIGIT ONE
+    assert_eq!(identifier_status(0x017F), IdentifierStatus::Restricted);
+    assert_eq!(identifier_status(0x2460), IdentifierStatus::Restricted);
+    assert_eq!(identifier_status(0x50000), IdentifierStatus::Restricted);
+
+    assert!(is_identifier_allowed('a' as u32));
+    assert!(!is_identifier_allowed('!' as u32));
+}
+
+#[test]
+fn check_identifier_types() {
+    assert_eq!(identifier_types('a' as u32), IDENTIFIER_TYPE_RECOMMENDED);
+    assert_eq!(identifier_types('\'' as u32), IDENTIFIER_TYPE_INCLUSION);
+    assert_eq!(identifier_types('!' as u32), IDENTIFIER_TYPE_NOT_XID);
+    assert_eq!(identifier_types(0xAD), IDENTIFIER_TYPE_DEFAULT_IGNORABLE);
+    // U+0149 LATIN SMALL LETTER N PRECEDED BY APOSTROPHE
+    assert_eq!(identifier_types(0x0149), IDENTIFIER_TYPE_DEPRECATED);
+    // U+2C80 COPTIC CAPITAL LETTER ALFA, U+A000 YI SYLLABLE IT
+    assert_eq!(identifier_types(0x2C80), IDENTIFIER_TYPE_EXCLUSION);
+    assert_eq!(identifier_types(0xA000), IDENTIFIER_TYPE_LIMITED_USE);
+
+    // Code points with several types
+    // U+017F LATIN SMALL LETTER LONG S
+    assert_eq!(
+        identifier_types(0x017F),
+        IDENTIFIER_TYPE_TECHNICAL | IDENTIFIER_TYPE_NOT_NFKC
+    );
+    // U+01C0 LATIN LETTER DENTAL CLICK
+    assert_eq!(
+        identifier_types(0x01C0),
+        IDENTIFIER_TYPE_TECHNICAL | IDENTIFIER_TYPE_UNCOMMON_USE | IDENTIFIER_TYPE_OBSOLETE
+    );
+
+    assert_eq!(identifier_types(0x50000), IDENTIFIER_TYPE_NOT_CHARACTER);
+}
+
+#[test]
+fn check_canonical_decomposition() {
+    // U+00C5 LATIN CAPITAL LETTER A WITH RING ABOVE, U+212B ANGSTROM SIGN
+    assert_eq!(canonical_decomposition(0xC5), Some(&[0x41, 0x30A][..]));
+    assert_eq!(canonical_decomposition(0x212B), Some(&[0x41, 0x30A][..]));
+    // U+1E68 LATIN CAPITAL LETTER S WITH DOT BELOW AND DOT ABOVE
+    assert_eq!(
+        canonical_decomposition(0x1E68),
+        Some(&[0x53, 0x323, 0x307][..])
+    );
+    // Compatibility decompositions aren't canonical.
+    assert_eq!(canonical_decomposition(0xFF21), None);
+    assert_eq!(canonical_decomposition('a' as u32), None);
+    assert_eq!(canonical_decomposition(0xAC00), None);
+}
+
+#[test]
+fn check_to_nfd() {
+    assert_eq!(to_nfd(&[]), []);
+    assert_eq!(to_nfd(&[0x61, 0x62]), [0x61, 0x62]);
+    assert_eq!(to_nfd(&[0xC5]), [0x41, 0x30A]);
+    // Combining marks are reordered by combining class.
+    assert_eq!(to_nfd(&[0x1E0B, 0x323]), [0x64, 0x323, 0x307]);
+    assert_eq!(to_nfd(&[0x64, 0x307, 0x323]), [0x64, 0x323, 0x307]);
+    assert_eq!(to_nfd(&[0xAC01]), [0x1100, 0x1161, 0x11A8]);
+    // Supplementary code points and unpaired surrogates are preserved.
+    assert_eq!(to_nfd(&[0xD835, 0xDC00, 0xDC00]), [0xD835, 0xDC00, 0xDC00]);
+}
+
+#[test]
+fn check_canonical_composition() {
+    assert_eq!(canonical_composition(0x41, 0x30A), Some(0xC5));
+    assert_eq!(canonical_composition(0x64, 0x307), Some(0x1E0B));
+    assert_eq!(canonical_composition(0x1100, 0x1161), Some(0xAC00));
+    assert_eq!(canonical_composition(0xAC00, 0x11A8), Some(0xAC01));
+    // Singletons and composition exclusions aren't composed to.
+    assert_eq!(canonical_composition(0x41, 0x301), Some(0xC1));
+    assert_eq!(canonical_composition(0x915, 0x93C), None);
+    assert_eq!(canonical_composition(0x61, 0x62), None);
+}
+
+#[test]
+fn check_to_nfc() {
+    assert_eq!(to_nfc(&[]), []);
+    assert_eq!(to_nfc(&[0x61, 0x62]), [0x61, 0x62]);
+    assert_eq!(to_nfc(&[0x41, 0x30A]), [0xC5]);
+    // U+212B ANGSTROM SIGN is a singleton decomposing to U+00C5.
+    assert_eq!(to_nfc(&[0x212B]), [0xC5]);
+    // U+0958 DEVANAGARI LETTER QA is excluded from composition.
+    assert_eq!(to_nfc(&[0x958]), [0x915, 0x93C]);
+    // A mark composes past a mark of lower combining class, but not past one
+    // of the same class.
+    assert_eq!(to_nfc(&[0x64, 0x307, 0x323]), [0x1E0D, 0x307]);
+    assert_eq!(to_nfc(&[0x61, 0x301, 0x301]), [0xE1, 0x301]);
+    // Hangul syllables
+    assert_eq!(to_nfc(&[0x1100, 0x1161, 0x11A8]), [0xAC01]);
+    assert_eq!(to_nfc(&[0xAC00, 0x11A8]), [0xAC01]);
+    // Supplementary code points and unpaired surrogates are preserved.
+    assert_eq!(to_nfc(&[0xD835, 0xDC00, 0xDC00]), [0xD835, 0xDC00, 0xDC00]);
+}
+
+#[test]
+fn check_skeleton() {
+    assert_eq!(confusable_prototype(0x0430), Some(&[0x61][..]));
+    assert_eq!(confusable_prototype('m' as u32), Some(&[0x72, 0x6E][..]));
+    assert_eq!(confusable_prototype('a' as u32), None);
+
+    // "раураl" in Cyrillic and Latin letters, and "paypal"
+    let mixed = [0x440, 0x430, 0x79, 0x440, 0x430, 0x6C];
+    let latin = [0x70, 0x61, 0x79, 0x70, 0x61, 0x6C];
+    assert_eq!(skeleton(&mixed), skeleton(&latin));
+    assert_eq!(skeleton(&latin), latin);
+
+    // "m" and "rn", "1", "I" and "l"
+    assert_eq!(skeleton(&[0x6D]), skeleton(&[0x72, 0x6E]));
+    assert_eq!(skeleton(&[0x31]), [0x6C]);
+    assert_eq!(skeleton(&[0x49]), [0x6C]);
+    assert_ne!(skeleton(&[0x61]), skeleton(&[0x62]));
+
+    // Prototypes apply to decomposed text, and the result is decomposed.
+    // U+00E5 LATIN SMALL LETTER A WITH RING ABOVE
+    assert_eq!(skeleton(&[0x430, 0x30A]), [0x61, 0x30A]);
+    assert_eq!(skeleton(&[0xE5]), [0x61, 0x30A]);
+
+    // Default-ignorable code points are removed.
+    assert_eq!(skeleton(&[0x61, 0xAD, 0x62]), [0x61, 0x62]);
+
+    // U+1D400 MATHEMATICAL BOLD CAPITAL A
+    assert_eq!(skeleton(&[0xD835, 0xDC00]), [0x41]);
+}
+
+#[test]
+fn check_script() {
+    assert_eq!(script('a' as u32), Script::Latn);
+    assert_eq!(script('1' as u32), Script::Zyyy);
+    assert_eq!(script(0x0301), Script::Zinh);
+    assert_eq!(script(0x0430), Script::Cyrl);
+    assert_eq!(script(0x3041), Script::Hira);
+    assert_eq!(script(0x4E00), Script::Hani);
+    assert_eq!(script(0x20000), Script::Hani);
+    assert_eq!(script(0x50000), Script::Zzzz);
+
+    let latin = script_extensions('a' as u32);
+    assert!(latin.contains(Script::Latn));
+    assert!(!latin.contains(Script::Cyrl));
+    // U+30FC KATAKANA-HIRAGANA PROLONGED SOUND MARK
+    let prolonged_sound_mark = script_extensions(0x30FC);
+    assert!(prolonged_sound_mark.contains(Script::Hira));
+    assert!(prolonged_sound_mark.contains(Script::Kana));
+    assert!(!prolonged_sound_mark.contains(Script::Zyyy));
+    assert!(script_extensions('1' as u32).contains(Script::Zyyy));
+}
+
+#[test]
+fn check_script_set() {
+    let mut set = ScriptSet::EMPTY;
+    assert!(set.is_empty());
+    set.insert(Script::Latn);
+    set.insert(Script::Kore);
+    assert!(set.contains(Script::Latn));
+    assert!(set.contains(Script::Kore));
+    assert!(!set.contains(Script::Grek));
+    assert!(!set.is_empty());
+
+    assert_eq!(set.intersection(ScriptSet::ALL), set);
+    assert!(set.intersection(ScriptSet::EMPTY).is_empty());
+    assert!(ScriptSet::ALL.contains(Script::Hanb));
+}
+
+#[test]
+fn check_augmented_script_extensions() {
+    assert_eq!(augmented_script_extensions('1' as u32), ScriptSet::ALL);
+    assert_eq!(augmented_script_extensions(0x0301), ScriptSet::ALL);
+
+    let han = augmented_script_extensions(0x4E00);
+    for script in [Script::Hani, Script::Hanb, Script::Jpan, Script::Kore] {
+        assert!(han.contains(script));
+    }
+    let hiragana = augmented_script_extensions(0x3041);
+    assert!(hiragana.contains(Script::Jpan));
+    assert!(!hiragana.contains(Script::Kore));
+    let hangul = augmented_script_extensions(0xAC00);
+    assert!(hangul.contains(Script::Kore));
+    assert!(!hangul.contains(Script::Jpan));
+    // U+3105 BOPOMOFO LETTER B
+    assert!(augmented_script_extensions(0x3105).contains(Script::Hanb));
+
+    assert!(resolved_script_set(&[0x61, 0x430]).is_empty());
+    assert!(resolved_script_set(&[0x61, 0x31]).contains(Script::Latn));
+    // Han and Hiragana resolve to Japanese.
+    let japanese = resolved_script_set(&[0x4E00, 0x3041]);
+    assert!(japanese.contains(Script::Jpan));
+    assert!(!japanese.contains(Script::Hani));
+    assert_eq!(resolved_script_set(&[]), ScriptSet::ALL);
+}
+
+#[test]
+fn check_restriction_level() {
+    assert_eq!(restriction_level(&[]), RestrictionLevel::AsciiOnly);
+    assert_eq!(
+        restriction_level(&[0x61, 0x31]),
+        RestrictionLevel::AsciiOnly
+    );
+    // Code points outside the identifier profile
+    assert_eq!(
+        restriction_level(&[0x61, 0x21]),
+        RestrictionLevel::Unrestricted
+    );
+    assert_eq!(
+        restriction_level(&[0x430, 0x2460]),
+        RestrictionLevel::Unrestricted
+    );
+
+    assert_eq!(
+        restriction_level(&[0xDF, 0x61]),
+        RestrictionLevel::SingleScript
+    );
+    assert_eq!(
+        restriction_level(&[0x430, 0x431]),
+        RestrictionLevel::SingleScript
+    );
+    // Han, Hiragana and Katakana
+    assert_eq!(
+        restriction_level(&[0x4E00, 0x3041, 0x30A2]),
+        RestrictionLevel::SingleScript
+    );
+
+    // Latin with Japanese, Chinese or Korean
+    assert_eq!(
+        restriction_level(&[0x61, 0x4E00, 0x3041]),
+        RestrictionLevel::HighlyRestrictive
+    );
+    assert_eq!(
+        restriction_level(&[0x61, 0x4E00, 0x3105]),
+        RestrictionLevel::HighlyRestrictive
+    );
+    assert_eq!(
+        restriction_level(&[0x61, 0xAC00, 0x4E00]),
+        RestrictionLevel::HighlyRestrictive
+    );
+
+    // Latin with one other Recommended script
+    assert_eq!(
+        restriction_level(&[0x61, 0x915]),
+        RestrictionLevel::ModeratelyRestrictive
+    );
+    assert_eq!(
+        restriction_level(&[0x61, 0x561]),
+        RestrictionLevel::ModeratelyRestrictive
+    );
+
+    // Latin with Cyrillic or Greek, or several other scripts
+    assert_eq!(
+        restriction_level(&[0x61, 0x430]),
+        RestrictionLevel::MinimallyRestrictive
+    );
+    assert_eq!(
+        restriction_level(&[0x61, 0x3B1]),
+        RestrictionLevel::MinimallyRestrictive
+    );
+    assert_eq!(
+        restriction_level(&[0x61, 0x915, 0x561]),
+        RestrictionLevel::MinimallyRestrictive
+    );
+}
+
+#[test]
+fn check_idna_status() {
+    assert_eq!(idna_status('a' as u32), IdnaStatus::Valid);
+    assert_eq!(idna_status('-' as u32), IdnaStatus::Valid);
+    assert_eq!(idna_status('A' as u32), IdnaStatus::Mapped);
+    assert_eq!(idna_status(0xAD), IdnaStatus::Ignored);
+    assert_eq!(idna_status(0xDF), IdnaStatus::Deviation);
+    assert_eq!(idna_status(0x200C), IdnaStatus::Deviation);
+    assert_eq!(idna_status(0x80), IdnaStatus::Disallowed);
+    assert_eq!(idna_status('_' as u32), IdnaStatus::DisallowedStd3Valid);
+    assert_eq!(idna_status(0xA0), IdnaStatus::DisallowedStd3Mapped);
+    assert_eq!(idna_status(0xD800), IdnaStatus::Disallowed);
+    // Unlisted code points
+    assert_eq!(idna_status(0x50000), IdnaStatus::Disallowed);
+}
+
+#[test]
+fn check_idna_mapping() {
+    assert_eq!(idna_mapping('A' as u32), ['a' as u16]);
+    assert_eq!(idna_mapping('Z' as u32), ['z' as u16]);
+    assert_eq!(idna_mapping(0xDF), [0x73, 0x73]);
+    // U+0130 LATIN CAPITAL LETTER I WITH DOT ABOVE
+    assert_eq!(idna_mapping(0x130), [0x69, 0x307]);
+    assert_eq!(idna_mapping(0xA0), [0x20]);
+    // U+10400 DESERET CAPITAL LETTER LONG I
+    assert_eq!(idna_mapping(0x10400), [0xD801, 0xDC28]);
+    // U+1D400 MATHEMATICAL BOLD CAPITAL A
+    assert_eq!(idna_mapping(0x1D400), ['a' as u16]);
+    assert!(idna_mapping('a' as u32).is_empty());
+    assert!(idna_mapping(0x200C).is_empty());
+    assert!(idna_mapping(0x50000).is_empty());
+
+    let mut elements = [0u16; 5];
+    let mut index = 1;
+    unsafe {
+        append_idna_mapping(0xDF, elements.as_mut_ptr(), &mut index);
+        append_idna_mapping(0x10400, elements.as_mut_ptr(), &mut index);
+    }
+    assert_eq!(length_idna_mapping(0xDF), 2);
+    assert_eq!(length_idna_mapping(0x10400), 2);
+    assert_eq!(index, 5);
+    assert_eq!(elements, [0, 0x73, 0x73, 0xD801, 0xDC28]);
+
+    // Nothing is written for a code point without a mapping.
+    unsafe {
+        append_idna_mapping('a' as u32, elements.as_mut_ptr(), &mut index);
+    }
+    assert_eq!(index, 5);
+}
+
+#[test]
+fn check_idna_map() {
+    let map = |text: &[u16], transitional, use_std3_ascii_rules| {
+        let mut output = vec![];
+        let valid = idna_map(text, transitional, use_std3_ascii_rules, &mut output);
+        (output, valid)
+    };
+
+    // "Example.COM"
+    let example = [
+        0x45, 0x78, 0x61, 0x6D, 0x70, 0x6C, 0x65, 0x2E, 0x43, 0x4F, 0x4D,
+    ];
+    let expected = [
+        0x65, 0x78, 0x61, 0x6D, 0x70, 0x6C, 0x65, 0x2E, 0x63, 0x6F, 0x6D,
+    ];
+    assert_eq!(map(&example, false, true), (expected.to_vec(), true));
+
+    // Ignored code points are removed, and U+3002 IDEOGRAPHIC FULL STOP maps to ".".
+    assert_eq!(
+        map(&[0x61, 0xAD, 0x3002, 0x62], false, true),
+        (vec![0x61, 0x2E, 0x62], true)
+    );
+
+    // Deviations are mapped only in transitional processing.
+    assert_eq!(
+        map(&[0xDF, 0x200D], false, true),
+        (vec![0xDF, 0x200D], true)
+    );
+    assert_eq!(map(&[0xDF, 0x200D], true, true), (vec![0x73, 0x73], true));
+
+    // Disallowed code points are kept, but make the result invalid.
+    assert_eq!(map(&[0x61, 0x80], false, false), (vec![0x61, 0x80], false));
+
+    // UseSTD3ASCIIRules
+    assert_eq!(map(&[0x61, 0x5F], false, true), (vec![0x61, 0x5F], false));
+    assert_eq!(map(&[0x61, 0x5F], false, false), (vec![0x61, 0x5F], true));
+    assert_eq!(map(&[0xA0], false, true), (vec![0xA0], false));
+    assert_eq!(map(&[0xA0], false, false), (vec![0x20], true));
+
+    // Supplementary code points
+    assert_eq!(
+        map(&[0xD801, 0xDC00, 0xD835, 0xDC00], false, true),
+        (vec![0xD801, 0xDC28, 0x61], true)
+    );
+}
+
+#[test]
+fn check_idna_process() {
+    const OPTIONS: IdnaOptions = IdnaOptions {
+        transitional: false,
+        use_std3_ascii_rules: true,
+        check_hyphens: true,
+        check_bidi: true,
+        check_joiners: true,
+    };
+
+    let process = |text: &str, options| {
+        let text: Vec<u16> = text.encode_utf16().collect();
+        let mut output = vec![];
+        let valid = idna_process(&text, options, &mut output);
+        (String::from_utf16(&output).unwrap(), valid)
+    };
+
+    // Mapping and normalization
+    assert_eq!(
+        process("Example.COM", OPTIONS),
+        ("example.com".to_string(), true)
+    );
+    assert_eq!(
+        process("Cafe\u{301}\u{3002}example", OPTIONS),
+        ("café.example".to_string(), true)
+    );
+    assert_eq!(process("", OPTIONS), ("".to_string(), true));
+    assert_eq!(
+        process("example.com.", OPTIONS),
+        ("example.com.".to_string(), true)
+    );
+
+    // Punycode labels are decoded, and validated under nontransitional
+    // processing.
+    assert_eq!(
+        process("xn--caf-dma.example", OPTIONS),
+        ("café.example".to_string(), true)
+    );
+    assert_eq!(process("XN--CAF-DMA", OPTIONS), ("café".to_string(), true));
+    assert_eq!(
+        process(
+            "xn--zca",
+            IdnaOptions {
+                transitional: true,
+                ..OPTIONS
+            }
+        ),
+        ("ß".to_string(), true)
+    );
+    assert_eq!(
+        process("xn--a-bga5120r", OPTIONS),
+        ("aé\u{10428}".to_string(), true)
+    );
+    // Undecodable labels are kept.
+    assert_eq!(
+        process("xn--caf-dma!", OPTIONS),
+        ("xn--caf-dma!".to_string(), false)
+    );
+    assert_eq!(
+        process("xn--café", OPTIONS),
+        ("xn--café".to_string(), false)
+    );
+    // Decoded labels must be non-ASCII, in NFC, and valid.
+    assert!(!process("xn--abc-", OPTIONS).1);
+    assert!(!process("xn--", OPTIONS).1);
+    assert_eq!(
+        process("xn--cafe-yvc", OPTIONS),
+        ("cafe\u{301}".to_string(), false)
+    );
+    assert_eq!(process("xn--ca-jga", OPTIONS), ("Éca".to_string(), false));
+
+    // Deviations
+    assert_eq!(process("faß", OPTIONS), ("faß".to_string(), true));
+    assert_eq!(
+        process(
+            "faß",
+            IdnaOptions {
+                transitional: true,
+                ..OPTIONS
+            }
+        ),
+        ("fass".to_string(), true)
+    );
+
+    // CheckHyphens
+    assert!(!process("-a.b", OPTIONS).1);
+    assert!(!process("a-.b", OPTIONS).1);
+    assert!(!process("ab--c", OPTIONS).1);
+    assert!(process("a-b--c", OPTIONS).1);
+    let no_hyphens = IdnaOptions {
+        check_hyphens: false,
+        ..OPTIONS
+    };
+    assert_eq!(
+        process("-a.ab--c", no_hyphens),
+        ("-a.ab--c".to_string(), true)
+    );
+    // Without CheckHyphens, a label mustn't begin with "xn--" once decoded.
+    assert_eq!(
+        process("xn--xn---epa", no_hyphens),
+        ("xn--é".to_string(), false)
+    );
+
+    // A label mustn't begin with a combining mark.
+    assert_eq!(
+        process("\u{301}a", OPTIONS),
+        ("\u{301}a".to_string(), false)
+    );
+    assert!(!process("a.\u{301}a", OPTIONS).1);
+
+    // Disallowed code points, and UseSTD3ASCIIRules
+    assert!(!process("a\u{80}", OPTIONS).1);
+    assert!(!process("a_b", OPTIONS).1);
+    assert_eq!(
+        process(
+            "a_b",
+            IdnaOptions {
+                use_std3_ascii_rules: false,
+                ..OPTIONS
+            }
+        ),
+        ("a_b".to_string(), true)
+    );
+
+    // CheckJoiners: U+0915 DEVANAGARI LETTER KA, U+094D DEVANAGARI SIGN VIRAMA
+    assert!(process("\u{915}\u{94D}\u{200D}", OPTIONS).1);
+    assert!(!process("a\u{200D}b", OPTIONS).1);
+    let no_joiners = IdnaOptions {
+        check_joiners: false,
+        ..OPTIONS
+    };
+    assert!(process("a\u{200D}b", no_joiners).1);
+    // The virama must immediately precede the joiner.
+    assert!(!process("\u{915}\u{94D}\u{301}\u{200D}", OPTIONS).1);
+    assert!(process("\u{915}\u{94D}\u{301}\u{200D}", no_joiners).1);
+
+    // CheckBidi: every label of a domain name with right-to-left code points
+    // must satisfy the Bidi Rule.
+    assert!(process("\u{5D0}\u{5D1}.example", OPTIONS).1);
+    assert_eq!(
+        process("xn--4dbc.example", OPTIONS),
+        ("\u{5D0}\u{5D1}.example".to_string(), true)
+    );
+    assert!(!process("\u{5D0}\u{5D1}.1a", OPTIONS).1);
+    assert!(process("a.1a", OPTIONS).1);
+    assert!(!process("\u{5D0}a", OPTIONS).1);
+    assert!(!process("\u{627}1\u{661}", OPTIONS).1);
+    assert!(process("\u{627}\u{661}", OPTIONS).1);
+    let no_bidi = IdnaOptions {
+        check_bidi: false,
+        ..OPTIONS
+    };
+    assert!(process("\u{5D0}\u{5D1}.1a", no_bidi).1);
+}
+
+#[test]
+fn check_nfkc_casefold() {
+    assert_eq!(nfkc_casefold('A' as u32), Some(&['a' as u32][..]));
+    assert_eq!(nfkc_casefold(0xDF), Some(&[0x73, 0x73][..]));
+    // U+00AA FEMININE ORDINAL INDICATOR, U+2160 ROMAN NUMERAL ONE
+    assert_eq!(nfkc_casefold(0xAA), Some(&['a' as u32][..]));
+    assert_eq!(nfkc_casefold(0x2160), Some(&['i' as u32][..]));
+    // U+FDFA ARABIC LIGATURE SALLALLAHOU ALAYHE WASALLAM
+    assert_eq!(nfkc_casefold(0xFDFA).map(<[u32]>::len), Some(18));
+    // U+10400 DESERET CAPITAL LETTER LONG I
+    assert_eq!(nfkc_casefold(0x10400), Some(&[0x10428][..]));
+    // Default-ignorable code points map to nothing.
+    assert_eq!(nfkc_casefold(0xAD), Some(&[][..]));
+    assert_eq!(nfkc_casefold(0xE0001), Some(&[][..]));
+    assert_eq!(nfkc_casefold('a' as u32), None);
+    assert_eq!(nfkc_casefold(0x50000), None);
+
+    assert!(changes_when_nfkc_casefolded('A' as u32));
+    assert!(changes_when_nfkc_casefolded(0xAD));
+    assert!(changes_when_nfkc_casefolded(0x1D400));
+    assert!(!changes_when_nfkc_casefolded('a' as u32));
+    assert!(!changes_when_nfkc_casefolded(0x10428));
+}
+
+#[test]
+fn check_to_nfkc_casefold() {
+    assert_eq!(to_nfkc_casefold(&[]), []);
+    assert_eq!(to_nfkc_casefold(&[0x61, 0x62]), [0x61, 0x62]);
+    // "Straße" and "STRASSE"
+    let strasse = [0x73, 0x74, 0x72, 0x61, 0x73, 0x73, 0x65];
+    assert_eq!(
+        to_nfkc_casefold(&[0x53, 0x74, 0x72, 0x61, 0xDF, 0x65]),
+        strasse
+    );
+    assert_eq!(
+        to_nfkc_casefold(&[0x53, 0x54, 0x52, 0x41, 0x53, 0x53, 0x45]),
+        strasse
+    );
+    // U+FB01 LATIN SMALL LIGATURE FI, U+00AD SOFT HYPHEN
+    assert_eq!(to_nfkc_casefold(&[0xFB01, 0xAD, 0x78]), [0x66, 0x69, 0x78]);
+    // U+212B ANGSTROM SIGN and U+00C5 fold alike.
+    assert_eq!(to_nfkc_casefold(&[0x212B]), to_nfkc_casefold(&[0xC5]));
+    // Supplementary code points, and unpaired surrogates
+    assert_eq!(
+        to_nfkc_casefold(&[0xD801, 0xDC00, 0xD835, 0xDC00]),
+        [0xD801, 0xDC28, 0x61]
+    );
+    assert_eq!(
+        to_nfkc_casefold(&[0x41, 0xDC00, 0xD801]),
+        [0x61, 0xDC00, 0xD801]
+    );
+}
+
+#[test]
+fn check_case_change_flags() {
+    assert_eq!(
+        case_change_flags('A' as u32),
+        CHANGES_WHEN_LOWERCASED | CHANGES_WHEN_CASEFOLDED | CHANGES_WHEN_CASEMAPPED
+    );
+    assert_eq!(
+        case_change_flags('a' as u32),
+        CHANGES_WHEN_UPPERCASED | CHANGES_WHEN_TITLECASED | CHANGES_WHEN_CASEMAPPED
+    );
+    assert_eq!(case_change_flags('1' as u32), 0);
+    assert_eq!(case_change_flags(0x50000), 0);
+
+    assert!(changes_when_lowercased('A' as u32));
+    assert!(!changes_when_lowercased('a' as u32));
+    assert!(changes_when_uppercased(0xDF));
+    assert!(!changes_when_uppercased('A' as u32));
+
+    // U+01C5 LATIN CAPITAL LETTER D WITH SMALL LETTER Z WITH CARON is already
+    // titlecase.
+    assert!(!changes_when_titlecased(0x1C5));
+    assert!(changes_when_titlecased(0x1C6));
+    assert!(changes_when_lowercased(0x1C5));
+    assert!(changes_when_uppercased(0x1C5));
+
+    // ß case-folds to "ss", but lowercases to itself.
+    assert!(changes_when_casefolded(0xDF));
+    assert!(!changes_when_lowercased(0xDF));
+    assert!(changes_when_casemapped(0xDF));
+
+    // U+10400 DESERET CAPITAL LETTER LONG I, U+10428 DESERET SMALL LETTER LONG I
+    assert!(changes_when_lowercased(0x10400));
+    assert!(changes_when_casefolded(0x10400));
+    assert!(changes_when_uppercased(0x10428));
+    assert!(!changes_when_casefolded(0x10428));
+    assert!(changes_when_casemapped(0x10428));
+}
+
+#[test]
+fn check_identifier_name() {
+    // "$foo1 = 2"
+    let text = [0x24, 0x66, 0x6F, 0x6F, 0x31, 0x20, 0x3D, 0x20, 0x32];
+    assert_eq!(identifier_name_prefix_length(&text), 5);
+    assert!(is_identifier_name(&text[..5]));
+    assert!(!is_identifier_name(&text));
+    assert_eq!(identifier_name_prefix_length(&text[4..]), 0);
+    assert!(!is_identifier_name(&text[4..5]));
+    assert!(!is_identifier_name(&[]));
+
+    // U+10400 DESERET CAPITAL LETTER LONG I, then U+11370 COMBINING GRANTHA
+    // LETTER A and ZERO WIDTH JOINER.
+    let text = [0xD801, 0xDC00, 0xD804, 0xDF70, 0x200D];
+    assert!(is_identifier_name(&text));
+    assert!(!is_identifier_name(&text[2..]));
+
+    // An unpaired surrogate ends the identifier.
+    assert_eq!(identifier_name_prefix_length(&[0x61, 0xD801, 0x62]), 1);
+    assert_eq!(identifier_name_prefix_length(&[0xD801, 0x61]), 0);
+}
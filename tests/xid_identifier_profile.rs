@@ -0,0 +1,64 @@
+use generate_unicode_data::generate_unicode_tables;
+
+generate_unicode_tables!(identifier_profile = xid);
+
+#[test]
+fn check_xid_ascii_tables() {
+    for code in 0..0x80u8 {
+        let c = code as char;
+        assert_eq!(
+            isxidstart[code as usize],
+            c.is_ascii_alphabetic(),
+            "{:?}",
+            c
+        );
+        assert_eq!(
+            isxidcontinue[code as usize],
+            c.is_ascii_alphanumeric() || c == '_',
+            "{:?}",
+            c
+        );
+    }
+}
+
+#[test]
+fn check_is_xid_start() {
+    assert!(is_xid_start('a' as u32));
+    assert!(is_xid_start('Z' as u32));
+    assert!(!is_xid_start('_' as u32));
+    assert!(!is_xid_start('$' as u32));
+    assert!(!is_xid_start('1' as u32));
+
+    assert!(is_xid_start(0xDF));
+    assert!(is_xid_start(0x4E00));
+    // U+037A GREEK YPOGEGRAMMENI is ID_Start, but not XID_Start, because its
+    // NFKC form begins with a combining mark.
+    assert!(!is_xid_start(0x37A));
+    assert!(!is_xid_start(0x300));
+
+    // U+10400 DESERET CAPITAL LETTER LONG I
+    assert!(is_xid_start(0x10400));
+    assert!(is_xid_start_non_bmp(0x10400));
+    assert!(is_xid_start(0x20000));
+    assert!(!is_xid_start(0x1D7CE));
+    assert!(!is_xid_start(0x50000));
+}
+
+#[test]
+fn check_is_xid_continue() {
+    assert!(is_xid_continue('a' as u32));
+    assert!(is_xid_continue('_' as u32));
+    assert!(is_xid_continue('9' as u32));
+    assert!(!is_xid_continue('$' as u32));
+    assert!(!is_xid_continue('-' as u32));
+
+    assert!(is_xid_continue(0x300));
+    assert!(is_xid_continue(0xB7));
+    assert!(!is_xid_continue(0x37A));
+
+    // U+1D7CE MATHEMATICAL BOLD DIGIT ZERO
+    assert!(is_xid_continue(0x1D7CE));
+    assert!(is_xid_continue_non_bmp(0x1D7CE));
+    assert!(is_xid_continue(0x10400));
+    assert!(!is_xid_continue(0x50000));
+}